@@ -0,0 +1,73 @@
+use std::any::Any;
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use conversation::Content;
+
+// the previous Content layout, every payload behind a Box
+fn boxed<T: Any + Send + 'static>(obj: T) -> Box<dyn Any + Send + 'static> {
+    Box::new(obj)
+}
+
+fn small(c: &mut Criterion) {
+    let mut group = c.benchmark_group("small");
+
+    group.bench_function("boxed", |b| b.iter(|| {
+        *black_box(boxed(black_box(42u32))).downcast::<u32>().unwrap()
+    }));
+
+    group.bench_function("content", |b| b.iter(|| {
+        black_box(Content::from(black_box(42u32))).into::<u32>().unwrap()
+    }));
+
+    group.finish();
+}
+
+fn large(c: &mut Criterion) {
+    let mut group = c.benchmark_group("large");
+
+    group.bench_function("boxed", |b| b.iter(|| {
+        *black_box(boxed(black_box([1u64; 8]))).downcast::<[u64; 8]>().unwrap()
+    }));
+
+    group.bench_function("content", |b| b.iter(|| {
+        black_box(Content::from(black_box([1u64; 8]))).into::<[u64; 8]>().unwrap()
+    }));
+
+    group.finish();
+}
+
+fn as_ref(c: &mut Criterion) {
+    let mut group = c.benchmark_group("as_ref");
+
+    let boxed = boxed(42u32);
+    let cont = Content::from(42u32);
+
+    group.bench_function("boxed", |b| b.iter(|| *black_box(&boxed).downcast_ref::<u32>().unwrap()));
+    group.bench_function("content", |b| b.iter(|| *black_box(&cont).as_ref::<u32>().unwrap()));
+
+    group.finish();
+}
+
+fn channel(c: &mut Criterion) {
+    let (boxed_tx, boxed_rx) = crossbeam_channel::unbounded();
+    let (cont_tx, cont_rx) = crossbeam_channel::unbounded();
+
+    let mut group = c.benchmark_group("channel");
+
+    group.bench_function("boxed", |b| b.iter(|| {
+        boxed_tx.send(boxed(black_box(42u32))).unwrap();
+        *boxed_rx.recv().unwrap().downcast::<u32>().unwrap()
+    }));
+
+    group.bench_function("content", |b| b.iter(|| {
+        cont_tx.send(Content::from(black_box(42u32))).unwrap();
+        cont_rx.recv().unwrap().into::<u32>().unwrap()
+    }));
+
+    group.finish();
+}
+
+criterion_group!(benches, small, large, as_ref, channel);
+criterion_main!(benches);
@@ -0,0 +1,83 @@
+use std::any::Any;
+use std::sync::{Arc, Mutex};
+
+use crate::channel::{self, MessageReceiver, MessageSender};
+
+/* ---------- */
+
+pub struct Publisher {
+    subscribers: Arc<Mutex<Vec<MessageSender>>>
+}
+
+impl Publisher {
+    pub fn subscribe(&self) -> MessageReceiver {
+        let (send, recv) = channel::channel();
+        self.add(send);
+
+        recv
+    }
+
+    pub fn subscribe_bounded(&self, cap: usize) -> MessageReceiver {
+        let (send, recv) = channel::channel_bounded(cap);
+        self.add(send);
+
+        recv
+    }
+
+    // a full bounded subscriber blocks this call, never the other publishers
+    pub fn publish<T: Clone + Any + Send + 'static>(&self, msg: T) -> usize {
+        let subscribers = match self.subscribers.lock() {
+            Ok(subscribers) => subscribers.clone(),
+            _ => return 0
+        };
+
+        let sent = subscribers.iter().filter(|sub| sub.send_msg(msg.clone()).is_ok()).count();
+
+        if sent < subscribers.len() {
+            self.prune();
+        }
+
+        sent
+    }
+
+    pub fn publish_shared<T: Any + Send + Sync + 'static>(&self, msg: T) -> usize {
+        self.publish(Arc::new(msg))
+    }
+
+    pub fn subscriber_count(&self) -> usize {
+        self.prune()
+    }
+
+    fn prune(&self) -> usize {
+        match self.subscribers.lock() {
+            Ok(mut subscribers) => {
+                subscribers.retain(|sub| sub.receiver_count() > 0);
+                subscribers.len()
+            }
+            _ => 0
+        }
+    }
+
+    fn add(&self, sender: MessageSender) {
+        if let Ok(mut subscribers) = self.subscribers.lock() {
+            subscribers.retain(|sub| sub.receiver_count() > 0);
+            subscribers.push(sender);
+        }
+    }
+}
+
+impl Clone for Publisher {
+    fn clone(&self) -> Self {
+        Self {
+            subscribers: Arc::clone(&self.subscribers)
+        }
+    }
+}
+
+/* ---------- */
+
+pub fn broadcast() -> Publisher {
+    Publisher {
+        subscribers: Arc::new(Mutex::new(Vec::new()))
+    }
+}
@@ -1,84 +1,670 @@
 use std::any::Any;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
 
-use crossbeam_channel::{Sender, Receiver};
+use crossbeam_channel::{Sender, Receiver, Select};
 
+use crate::envelope::Envelope;
 use crate::message::{Message, Content};
 use crate::{RecvError, SendMessageError, SendReplyError, IntoMessage};
+use crate::{TrySendMessageError, SendMessageTimeoutError, TryRecvError, RecvTimeoutError};
+use crate::ReplyError;
+use crate::signal::{self, Signal, Notifier, Recv};
+use crate::iter::{Iter, TryIter, IterOf};
+use crate::stats::{ChannelState, ChannelStats};
+
+/* ---------- */
+
+const LANES: usize = 4;
+
+pub(crate) type Letter = (Envelope, Message);
+
+type ReplySlot = Result<Content, ReplyError>;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Priority {
+    Low,
+    #[default]
+    Normal,
+    High,
+    Urgent
+}
+
+/* ---------- */
+
+// A bounded channel keeps its lanes unbounded and admits letters through a gate
+// instead: a send takes one of `cap` permits that the receive hands back, or
+// with no capacity at all it waits for a receiver to ask for a letter.
+#[derive(Clone)]
+pub(crate) enum Gate {
+    Permits(Sender<()>),
+    Demand(Receiver<()>)
+}
+
+impl Gate {
+    fn pass(&self, msg: Message) -> Result<Message, SendMessageError> {
+        let passed = match self {
+            Self::Permits(permits) => permits.send(()).is_ok(),
+            Self::Demand(demand) => demand.recv().is_ok()
+        };
+
+        match passed {
+            true => Ok(msg),
+            false => Err(crossbeam_channel::SendError((Envelope::new(), msg)).into())
+        }
+    }
+
+    fn try_pass(&self, msg: Message) -> Result<Message, TrySendMessageError> {
+        let full = match self {
+            Self::Permits(permits) => match permits.try_send(()) {
+                Ok(()) => return Ok(msg),
+                Err(err) => err.is_full()
+            },
+            Self::Demand(demand) => match demand.try_recv() {
+                Ok(()) => return Ok(msg),
+                Err(err) => err.is_empty()
+            }
+        };
+
+        match full {
+            true => Err(TrySendMessageError::Full(msg)),
+            false => Err(TrySendMessageError::Disconnected(msg))
+        }
+    }
+
+    fn pass_timeout(&self, msg: Message, timeout: Duration) -> Result<Message, SendMessageTimeoutError> {
+        let timed_out = match self {
+            Self::Permits(permits) => match permits.send_timeout((), timeout) {
+                Ok(()) => return Ok(msg),
+                Err(err) => err.is_timeout()
+            },
+            Self::Demand(demand) => match demand.recv_timeout(timeout) {
+                Ok(()) => return Ok(msg),
+                Err(err) => err.is_timeout()
+            }
+        };
+
+        match timed_out {
+            true => Err(SendMessageTimeoutError::Timeout(msg)),
+            false => Err(SendMessageTimeoutError::Disconnected(msg))
+        }
+    }
+
+    fn capacity(&self) -> usize {
+        match self {
+            Self::Permits(permits) => permits.capacity().unwrap_or(0),
+            Self::Demand(_) => 0
+        }
+    }
+}
+
+#[derive(Clone)]
+pub(crate) enum GateKeeper {
+    Permits(Receiver<()>),
+    Demand(Sender<()>)
+}
+
+impl GateKeeper {
+    fn taken(&self) {
+        if let Self::Permits(permits) = self {
+            let _ = permits.try_recv();
+        }
+    }
+
+    // a single outstanding request is enough, it lets exactly one letter in
+    fn waiting(&self) {
+        if let Self::Demand(demand) = self {
+            let _ = demand.try_send(());
+        }
+    }
+
+    fn capacity(&self) -> usize {
+        match self {
+            Self::Permits(permits) => permits.capacity().unwrap_or(0),
+            Self::Demand(_) => 0
+        }
+    }
+}
 
 /* ---------- */
 
 pub struct MessageSender {
-    msg_sender: Sender<Message>,
-    reply_recver: Receiver<Content>
+    lanes: [Sender<Letter>; LANES],
+    gate: Option<Gate>,
+    notifier: Notifier,
+    state: Arc<ChannelState>
 }
 
 impl MessageSender {
     pub fn send_msg<T: Any + Send + 'static>(&self, msg: T) -> Result<(), SendMessageError> {
-        Ok(self.msg_sender.send(msg.into_msg())?)
+        self.send_msg_priority(Priority::Normal, msg)
+    }
+
+    pub fn send_msg_priority<T>(&self, level: Priority, msg: T) -> Result<(), SendMessageError>
+    where
+        T: Any + Send + 'static
+    {
+        self.send(level, msg.into_msg())
+    }
+
+    pub fn send_msg_with_reply<T: Any + Send + 'static>(&self, msg: T) -> Result<ReplyReceiver, SendMessageError> {
+        self.send_msg_with_reply_priority(Priority::Normal, msg)
+    }
+
+    pub fn send_msg_with_reply_priority<T>(&self, level: Priority, msg: T) -> Result<ReplyReceiver, SendMessageError>
+    where
+        T: Any + Send + 'static
+    {
+        let (msg, reply) = msg.into_msg_with_reply();
+        self.send(level, msg)?;
+
+        Ok(reply)
+    }
+
+    pub fn request<T, R>(&self, msg: T) -> Result<PendingReply<R>, SendMessageError>
+    where
+        T: Any + Send + 'static,
+        R: Any + Send + 'static
+    {
+        Ok(PendingReply::from(self.send_msg_with_reply(msg)?))
+    }
+
+    pub fn send_msg_deadline<T>(&self, msg: T, deadline: Instant) -> Result<(), SendMessageError>
+    where
+        T: Any + Send + 'static
+    {
+        self.send_msg_with_envelope(Envelope::new().with_deadline(deadline), msg)
+    }
+
+    pub fn request_deadline<T, R>(&self, msg: T, deadline: Instant) -> Result<PendingReply<R>, SendMessageError>
+    where
+        T: Any + Send + 'static,
+        R: Any + Send + 'static
+    {
+        self.request_with_envelope(Envelope::new().with_deadline(deadline), msg)
+    }
+
+    pub fn send_msg_with_envelope<T>(&self, envelope: Envelope, msg: T) -> Result<(), SendMessageError>
+    where
+        T: Any + Send + 'static
+    {
+        self.send_letter(Priority::Normal, envelope, msg.into_msg())
+    }
+
+    pub fn request_with_envelope<T, R>(&self, envelope: Envelope, msg: T) -> Result<PendingReply<R>, SendMessageError>
+    where
+        T: Any + Send + 'static,
+        R: Any + Send + 'static
+    {
+        let (msg, reply) = msg.into_msg_with_reply();
+        self.send_letter(Priority::Normal, envelope, msg)?;
+
+        Ok(PendingReply::from(reply))
+    }
+
+    pub fn try_send_msg<T: Any + Send + 'static>(&self, msg: T) -> Result<(), TrySendMessageError> {
+        let msg = match &self.gate {
+            Some(gate) => gate.try_pass(msg.into_msg())?,
+            None => msg.into_msg()
+        };
+
+        self.lane(Priority::Normal).try_send((Envelope::new(), msg))?;
+        self.notifier.notify();
+
+        if self.state.is_enabled() {
+            self.state.record_sent(self.len());
+        }
+
+        Ok(())
+    }
+
+    pub fn send_msg_timeout<T>(&self, msg: T, timeout: Duration) -> Result<(), SendMessageTimeoutError>
+    where
+        T: Any + Send + 'static
+    {
+        let msg = match &self.gate {
+            Some(gate) => gate.pass_timeout(msg.into_msg(), timeout)?,
+            None => msg.into_msg()
+        };
+
+        self.lane(Priority::Normal).send_timeout((Envelope::new(), msg), timeout)?;
+        self.notifier.notify();
+
+        if self.state.is_enabled() {
+            self.state.record_sent(self.len());
+        }
+
+        Ok(())
+    }
+
+    pub fn send_disconnect(&self, level: Priority) -> Result<(), SendMessageError> {
+        self.send(level, Message::Disconnect)
+    }
+
+    pub fn len(&self) -> usize {
+        self.lanes.iter().map(Sender::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lanes.iter().all(Sender::is_empty)
+    }
+
+    pub fn capacity(&self) -> Option<usize> {
+        self.gate.as_ref().map(Gate::capacity)
     }
 
-    pub fn send_msg_with_reply<T: Any + Send + 'static>(&self, msg: T) -> Result<(), SendMessageError> {
-        Ok(self.msg_sender.send(msg.into_msg_with_reply())?)
+    pub fn sender_count(&self) -> usize {
+        self.state.sender_count()
     }
 
-    pub fn recv_reply(&self) -> Result<Content, RecvError> {
-        Ok(self.reply_recver.recv()?)
+    pub fn receiver_count(&self) -> usize {
+        self.state.receiver_count()
+    }
+
+    pub fn enable_stats(&self) {
+        self.state.enable()
+    }
+
+    pub fn stats(&self) -> Option<ChannelStats> {
+        self.state.snapshot()
     }
 
     pub(crate) fn disconnect(&self) {
-        let _ = self.msg_sender.send(Message::Disconnect);
+        let _ = self.send_disconnect(Priority::Low);
     }
 
-    pub(crate) fn from(msg_send: Sender<Message>, reply_recv: Receiver<Content>) -> Self {
+    pub(crate) fn from(lanes: [Sender<Letter>; LANES], gate: Option<Gate>, signal: &Arc<Signal>, state: &Arc<ChannelState>) -> Self {
+        state.add_sender();
+
         Self {
-            msg_sender: msg_send,
-            reply_recver: reply_recv
+            lanes,
+            gate,
+            notifier: Notifier::new(signal),
+            state: Arc::clone(state)
         }
     }
+
+    fn send(&self, level: Priority, msg: Message) -> Result<(), SendMessageError> {
+        self.send_letter(level, Envelope::new(), msg)
+    }
+
+    fn send_letter(&self, level: Priority, envelope: Envelope, msg: Message) -> Result<(), SendMessageError> {
+        let msg = match msg {
+            Message::WithReply(data, reply_to) if self.state.is_enabled() => {
+                Message::WithReply(data, reply_to.with_origin(&self.state))
+            }
+            msg => msg
+        };
+
+        let msg = match &self.gate {
+            Some(gate) => gate.pass(msg)?,
+            None => msg
+        };
+
+        self.lane(level).send((envelope, msg))?;
+        self.notifier.notify();
+
+        if self.state.is_enabled() {
+            self.state.record_sent(self.len());
+        }
+
+        Ok(())
+    }
+
+    fn lane(&self, level: Priority) -> &Sender<Letter> {
+        &self.lanes[level as usize]
+    }
 }
 
 impl Clone for MessageSender {
     fn clone(&self) -> Self {
-        Self {
-            msg_sender: self.msg_sender.clone(),
-            reply_recver: self.reply_recver.clone()
-        }
+        Self::from(self.lanes.clone(), self.gate.clone(), self.notifier.signal(), &self.state)
+    }
+}
+
+impl Drop for MessageSender {
+    fn drop(&mut self) {
+        self.state.remove_sender()
     }
 }
 
 /* ---------- */
 
 pub struct MessageReceiver {
-    msg_recver: Receiver<Message>,
-    reply_sender: Sender<Content>
+    lanes: [Receiver<Letter>; LANES],
+    gate: Option<GateKeeper>,
+    signal: Arc<Signal>,
+    state: Arc<ChannelState>
 }
 
 impl MessageReceiver {
     pub fn recv_msg(&self) -> Result<Message, RecvError> {
-        Ok(self.msg_recver.recv()?)
+        self.recv_envelope().map(|(_, msg)| msg)
+    }
+
+    pub fn try_recv_msg(&self) -> Result<Message, TryRecvError> {
+        self.try_recv_envelope().map(|(_, msg)| msg)
+    }
+
+    pub fn recv_envelope(&self) -> Result<(Envelope, Message), RecvError> {
+        loop {
+            match self.try_recv_envelope() {
+                Ok(letter) => return Ok(letter),
+                Err(TryRecvError::Disconnected) => return Err(RecvError),
+                Err(TryRecvError::Empty) => self.select().ready()
+            };
+        }
+    }
+
+    pub fn try_recv_envelope(&self) -> Result<(Envelope, Message), TryRecvError> {
+        loop {
+            let (envelope, msg) = self.try_recv_letter()?;
+
+            if !envelope.is_expired() {
+                return Ok((envelope, msg))
+            }
+
+            self.expire(msg);
+        }
+    }
+    pub fn recv_msg_timeout(&self, timeout: Duration) -> Result<Message, RecvTimeoutError> {
+        self.recv_msg_deadline(Instant::now() + timeout)
+    }
+
+    pub fn recv_msg_deadline(&self, deadline: Instant) -> Result<Message, RecvTimeoutError> {
+        loop {
+            match self.try_recv_msg() {
+                Ok(msg) => return Ok(msg),
+                Err(TryRecvError::Disconnected) => return Err(RecvTimeoutError::Disconnected),
+                Err(TryRecvError::Empty) => {
+                    if self.select().ready_deadline(deadline).is_err() {
+                        return Err(RecvTimeoutError::Timeout)
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn recv_msg_async(&self) -> impl Future<Output = Result<Message, RecvError>> + '_ {
+        Recv::new(|| self.try_recv_msg(), &self.signal)
+    }
+
+    pub fn iter(&self) -> Iter<'_> {
+        Iter::new(self)
+    }
+
+    pub fn try_iter(&self) -> TryIter<'_> {
+        TryIter::new(self)
+    }
+
+    pub fn iter_of<T: Any + Send + 'static>(&self) -> IterOf<'_, T> {
+        IterOf::new(self)
+    }
+
+    #[cfg(feature = "stream")]
+    pub fn into_stream(self) -> crate::iter::MessageStream {
+        crate::iter::MessageStream::new(self)
     }
 
-    pub fn send_reply(&self, reply: Content) -> Result<(), SendReplyError> {
-        Ok(self.reply_sender.send(reply)?)
+    pub fn send_reply(&self, to: ReplySender, reply: Content) -> Result<(), SendReplyError> {
+        to.send(reply)
     }
 
-    pub(crate) fn from(msg_recv: Receiver<Message>, reply_send: Sender<Content>) -> Self {
+    pub fn len(&self) -> usize {
+        self.lanes.iter().map(Receiver::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lanes.iter().all(Receiver::is_empty)
+    }
+
+    pub fn capacity(&self) -> Option<usize> {
+        self.gate.as_ref().map(GateKeeper::capacity)
+    }
+
+    pub fn sender_count(&self) -> usize {
+        self.state.sender_count()
+    }
+
+    pub fn receiver_count(&self) -> usize {
+        self.state.receiver_count()
+    }
+
+    pub fn enable_stats(&self) {
+        self.state.enable()
+    }
+
+    pub fn stats(&self) -> Option<ChannelStats> {
+        self.state.snapshot()
+    }
+
+    #[cfg_attr(not(feature = "stream"), allow(dead_code))]
+    pub(crate) fn poll_recv_msg(&self, cx: &mut Context<'_>) -> Poll<Result<Message, RecvError>> {
+        signal::poll_recv(|| self.try_recv_msg(), &self.signal, cx)
+    }
+
+    pub(crate) fn from(lanes: [Receiver<Letter>; LANES], gate: Option<GateKeeper>, signal: &Arc<Signal>, state: &Arc<ChannelState>) -> Self {
+        state.add_receiver();
+
         Self {
-            msg_recver: msg_recv,
-            reply_sender: reply_send
+            lanes,
+            gate,
+            signal: Arc::clone(signal),
+            state: Arc::clone(state)
+        }
+    }
+
+    // the lanes to wait on, a zero capacity sender can only get in once the receiver waits
+    pub(crate) fn wait_lanes(&self) -> &[Receiver<Letter>] {
+        if let Some(gate) = &self.gate {
+            gate.waiting();
         }
+
+        &self.lanes
     }
 
-    pub(crate) fn msg_recver(&self) -> &Receiver<Message> {
-        &self.msg_recver
+    fn try_recv_letter(&self) -> Result<Letter, TryRecvError> {
+        let letter = self.take_letter();
+
+        if let Some(gate) = &self.gate {
+            match &letter {
+                Ok(_) => gate.taken(),
+                Err(TryRecvError::Empty) => gate.waiting(),
+                Err(_) => {}
+            }
+        }
+
+        letter
+    }
+
+    fn take_letter(&self) -> Result<Letter, TryRecvError> {
+        let mut disconnected = false;
+
+        for lane in self.lanes.iter().rev() {
+            match lane.try_recv() {
+                Ok(letter) => return Ok(letter),
+                Err(crossbeam_channel::TryRecvError::Disconnected) => disconnected = true,
+                Err(crossbeam_channel::TryRecvError::Empty) => {}
+            }
+        }
+
+        if !disconnected {
+            return Err(TryRecvError::Empty)
+        }
+
+        // a lower lane may have been filled right before the last sender left
+        match self.lanes.iter().rev().find_map(|lane| lane.try_recv().ok()) {
+            Some(letter) => Ok(letter),
+            None => Err(TryRecvError::Disconnected)
+        }
+    }
+
+    pub(crate) fn fail_pending(&self, err: ReplyError) {
+        while let Ok(msg) = self.try_recv_msg() {
+            if let Message::WithReply(_, reply_to) = msg {
+                reply_to.fail(err.clone());
+            }
+        }
+    }
+
+    fn expire(&self, msg: Message) {
+        if let Message::WithReply(_, reply_to) = msg {
+            reply_to.fail(ReplyError::Expired);
+        }
+
+        if self.state.is_enabled() {
+            self.state.record_expired();
+        }
+    }
+
+    fn select(&self) -> Select<'_> {
+        let mut sel = Select::new();
+        self.lanes.iter().for_each(|lane| {
+            sel.recv(lane);
+        });
+
+        sel
     }
 }
 
 impl Clone for MessageReceiver {
     fn clone(&self) -> Self {
+        Self::from(self.lanes.clone(), self.gate.clone(), &self.signal, &self.state)
+    }
+}
+
+impl Drop for MessageReceiver {
+    fn drop(&mut self) {
+        self.state.remove_receiver()
+    }
+}
+
+/* ---------- */
+
+pub struct ReplySender {
+    reply_sender: Sender<ReplySlot>,
+    notifier: Notifier,
+    origin: Option<Arc<ChannelState>>
+}
+
+impl ReplySender {
+    pub fn send(self, reply: Content) -> Result<(), SendReplyError> {
+        if let Err(crossbeam_channel::SendError(Ok(reply))) = self.reply_sender.send(Ok(reply)) {
+            return Err(SendReplyError::from(crossbeam_channel::SendError(reply)))
+        }
+
+        self.notifier.notify();
+
+        if let Some(origin) = &self.origin {
+            origin.record_reply();
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn fail(self, err: ReplyError) {
+        let _ = self.reply_sender.send(Err(err));
+        self.notifier.notify();
+    }
+
+    fn with_origin(mut self, state: &Arc<ChannelState>) -> Self {
+        self.origin = Some(Arc::clone(state));
+        self
+    }
+}
+
+/* ---------- */
+
+pub struct ReplyReceiver {
+    reply_recver: Receiver<ReplySlot>,
+    signal: Arc<Signal>
+}
+
+impl ReplyReceiver {
+    pub fn recv(&self) -> Result<Content, ReplyError> {
+        self.reply_recver.recv().map_err(RecvError::from)?
+    }
+
+    pub fn try_recv(&self) -> Result<Content, ReplyError> {
+        self.reply_recver.try_recv().map_err(TryRecvError::from)?
+    }
+
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<Content, ReplyError> {
+        self.reply_recver.recv_timeout(timeout).map_err(RecvTimeoutError::from)?
+    }
+
+    pub fn recv_deadline(&self, deadline: Instant) -> Result<Content, ReplyError> {
+        self.reply_recver.recv_deadline(deadline).map_err(RecvTimeoutError::from)?
+    }
+
+    pub async fn recv_async(&self) -> Result<Content, ReplyError> {
+        Recv::new(|| self.reply_recver.try_recv().map_err(TryRecvError::from), &self.signal).await?
+    }
+
+    pub fn is_ready(&self) -> bool {
+        !self.reply_recver.is_empty()
+    }
+
+    pub(crate) fn reply_recver(&self) -> &Receiver<ReplySlot> {
+        &self.reply_recver
+    }
+}
+
+/* ---------- */
+
+pub struct PendingReply<R> {
+    reply: ReplyReceiver,
+    _type: PhantomData<fn() -> R>
+}
+
+impl<R: Any + Send + 'static> PendingReply<R> {
+    pub fn wait(self) -> Result<R, ReplyError> {
+        Self::convert(self.reply.recv()?)
+    }
+
+    pub fn wait_timeout(&self, timeout: Duration) -> Result<R, ReplyError> {
+        Self::convert(self.reply.recv_timeout(timeout)?)
+    }
+
+    pub fn wait_deadline(&self, deadline: Instant) -> Result<R, ReplyError> {
+        Self::convert(self.reply.recv_deadline(deadline)?)
+    }
+
+    pub async fn wait_async(&self) -> Result<R, ReplyError> {
+        Self::convert(self.reply.recv_async().await?)
+    }
+
+    pub fn try_get(&self) -> Result<R, ReplyError> {
+        Self::convert(self.reply.try_recv()?)
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.reply.is_ready()
+    }
+
+    pub fn into_inner(self) -> ReplyReceiver {
+        self.reply
+    }
+
+    fn convert(reply: Content) -> Result<R, ReplyError> {
+        Ok(reply.convert::<R>()?)
+    }
+}
+
+impl<R> PendingReply<R> {
+    pub(crate) fn reply_recver(&self) -> &ReplyReceiver {
+        &self.reply
+    }
+}
+
+impl<R> From<ReplyReceiver> for PendingReply<R> {
+    fn from(reply: ReplyReceiver) -> Self {
         Self {
-            msg_recver: self.msg_recver.clone(),
-            reply_sender: self.reply_sender.clone()
+            reply,
+            _type: PhantomData
         }
     }
 }
@@ -86,8 +672,50 @@ impl Clone for MessageReceiver {
 /* ---------- */
 
 pub fn channel() -> (MessageSender, MessageReceiver) {
-    let (msg_send, msg_recv) = crossbeam_channel::unbounded();
-    let (reply_send, reply_recv) = crossbeam_channel::unbounded();
+    lanes(None)
+}
+
+pub fn channel_bounded(cap: usize) -> (MessageSender, MessageReceiver) {
+    let gate = match cap {
+        0 => {
+            let (demand_send, demand_recv) = crossbeam_channel::bounded(1);
+            (Gate::Demand(demand_recv), GateKeeper::Demand(demand_send))
+        }
+        cap => {
+            let (permit_send, permit_recv) = crossbeam_channel::bounded(cap);
+            (Gate::Permits(permit_send), GateKeeper::Permits(permit_recv))
+        }
+    };
+
+    lanes(Some(gate))
+}
+
+fn lanes(gate: Option<(Gate, GateKeeper)>) -> (MessageSender, MessageReceiver) {
+    let lanes: [_; LANES] = std::array::from_fn(|_| crossbeam_channel::unbounded());
+    let senders = std::array::from_fn(|idx| lanes[idx].0.clone());
+    let recvers = lanes.map(|(_, recv)| recv);
+    let signal = Arc::new(Signal::default());
+    let state = Arc::new(ChannelState::default());
+
+    let (gate, keeper) = gate.unzip();
+
+    (MessageSender::from(senders, gate, &signal, &state), MessageReceiver::from(recvers, keeper, &signal, &state))
+}
+
+pub(crate) fn reply_channel() -> (ReplySender, ReplyReceiver) {
+    let (reply_send, reply_recv) = crossbeam_channel::bounded(1);
+    let signal = Arc::new(Signal::default());
+
+    let sender = ReplySender {
+        reply_sender: reply_send,
+        notifier: Notifier::new(&signal),
+        origin: None
+    };
+
+    let recver = ReplyReceiver {
+        reply_recver: reply_recv,
+        signal
+    };
 
-    (MessageSender::from(msg_send, reply_recv), MessageReceiver::from(msg_recv, reply_send))
+    (sender, recver)
 }
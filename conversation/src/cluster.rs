@@ -2,13 +2,19 @@ use std::any::Any;
 use std::collections::HashMap;
 use std::ops::{Deref, DerefMut};
 use std::thread::{self, JoinHandle};
+use std::time::Instant;
 
 use crossbeam_channel::{Sender, Receiver, Select};
 
-use crate::{IntoMessage, Unit, MessageReceiver, MessageSender};
-use crate::error::{RecvError, SendReplyError, ClusterError};
+use crate::{Unit, MessageReceiver, MessageSender, ReplySender, PendingReply, Priority};
+use crate::error::{TryRecvError, SendReplyError, ClusterError, ReplyError};
+use crate::channel::Letter;
+use crate::envelope::Envelope;
+use crate::panic;
+use crate::request::Request;
 use crate::message::{Message, Content};
-use crate::unit::Builder;
+use crate::supervisor::Monitor;
+use crate::unit::{self, Builder, StopReason};
 
 /* ---------- */
 
@@ -39,7 +45,7 @@ impl Cluster {
 }
 
 impl Cluster {
-    pub fn register<T>(&mut self, obj: T) -> Builder<T>
+    pub fn register<T>(&mut self, obj: T) -> Builder<'_, T>
     where
         T: Unit + Send + 'static
     {
@@ -48,7 +54,7 @@ impl Cluster {
 
     pub fn remove<'a>(&mut self, id: &'a str) -> Result<(), ClusterError<'a>> {
         if let Some(sender) = self.msger_pool.remove(id) {
-            let _ = sender.send_msg(Message::Disconnect);
+            sender.disconnect();
             return Ok(())
         }
 
@@ -56,11 +62,18 @@ impl Cluster {
     }
 
     pub fn send_to<'a, T>(&self, id: &'a str, data: T) -> Result<(), ClusterError<'a>>
+    where
+        T: Any + Send + 'static
+    {
+        self.send_to_priority(id, Priority::Normal, data)
+    }
+
+    pub fn send_to_priority<'a, T>(&self, id: &'a str, level: Priority, data: T) -> Result<(), ClusterError<'a>>
     where
         T: Any + Send + 'static
     {
         if let Some(sender) = self.msger_pool.get(id) {
-            if sender.send_msg(data.into_msg()).is_err() {
+            if sender.send_msg_priority(level, data).is_err() {
                 return Err(ClusterError::AlreadyDisconnected)
             }
 
@@ -70,18 +83,45 @@ impl Cluster {
         Err(ClusterError::IdNotFound(id))
     }
 
+    pub fn send_to_many<'a, T>(&self, ids: &[&'a str], data: T) -> Result<(), ClusterError<'a>>
+    where
+        T: Clone + Any + Send + Sync + 'static
+    {
+        let senders = ids.iter()
+            .map(|id| self.msger_pool.get(id).ok_or(ClusterError::IdNotFound(id)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let (last, others) = match senders.split_last() {
+            Some(senders) => senders,
+            None => return Ok(())
+        };
+
+        let data = Content::shared(data);
+
+        for sender in others {
+            if sender.send_msg(data.share().expect("shared content")).is_err() {
+                return Err(ClusterError::AlreadyDisconnected)
+            }
+        }
+
+        last.send_msg(data).map_err(|_| ClusterError::AlreadyDisconnected)
+    }
+
     pub fn send_to_with_reply<'a, T, R>(&self, id: &'a str, data: T) -> Result<R, ClusterError<'a>>
     where
         T: Any + Send + 'static,
         R: Any + Send + 'static,
     {
         if let Some(sender) = self.msger_pool.get(id) {
-            if sender.send_msg_with_reply(data).is_err() {
-                return Err(ClusterError::AlreadyDisconnected)
-            }
+            let reply = match sender.send_msg_with_reply(data) {
+                Ok(reply) => reply,
+                _ => return Err(ClusterError::AlreadyDisconnected)
+            };
 
-            match sender.recv_reply() {
-                Ok(reply) => return reply.into::<R>().ok_or(ClusterError::ContentConversionError),
+            match reply.recv() {
+                Ok(reply) => return reply.convert::<R>().map_err(ClusterError::ContentConversionError),
+                Err(ReplyError::Expired) => return Err(ClusterError::Expired),
+                Err(ReplyError::UnitPanicked(msg)) => return Err(ClusterError::UnitPanicked(msg)),
                 _ => return Err(ClusterError::AlreadyDisconnected)
             }
         }
@@ -89,12 +129,70 @@ impl Cluster {
         Err(ClusterError::IdNotFound(id))
     }
 
-    pub(crate) fn add_unique<T>(&mut self, id: &'static str, obj: T, tx: MessageSender, rx: MessageReceiver) -> Result<(), ClusterError>
+    pub fn ask<'a, Q: Request>(&self, id: &'a str, req: Q) -> Result<Q::Response, ClusterError<'a>> {
+        self.send_to_with_reply::<Q, Q::Response>(id, req)
+    }
+
+    pub fn request<'a, T, R>(&self, id: &'a str, data: T) -> Result<PendingReply<R>, ClusterError<'a>>
+    where
+        T: Any + Send + 'static,
+        R: Any + Send + 'static,
+    {
+        if let Some(sender) = self.msger_pool.get(id) {
+            return sender.request(data).map_err(|_| ClusterError::AlreadyDisconnected)
+        }
+
+        Err(ClusterError::IdNotFound(id))
+    }
+
+    pub fn send_to_deadline<'a, T>(&self, id: &'a str, data: T, deadline: Instant) -> Result<(), ClusterError<'a>>
+    where
+        T: Any + Send + 'static
+    {
+        self.send_to_with_envelope(id, Envelope::new().with_deadline(deadline), data)
+    }
+
+    pub fn request_deadline<'a, T, R>(&self, id: &'a str, data: T, deadline: Instant) -> Result<PendingReply<R>, ClusterError<'a>>
+    where
+        T: Any + Send + 'static,
+        R: Any + Send + 'static,
+    {
+        self.request_with_envelope(id, Envelope::new().with_deadline(deadline), data)
+    }
+
+    pub fn send_to_with_envelope<'a, T>(&self, id: &'a str, envelope: Envelope, data: T) -> Result<(), ClusterError<'a>>
+    where
+        T: Any + Send + 'static
+    {
+        if let Some(sender) = self.msger_pool.get(id) {
+            return sender.send_msg_with_envelope(envelope, data).map_err(|_| ClusterError::AlreadyDisconnected)
+        }
+
+        Err(ClusterError::IdNotFound(id))
+    }
+
+    pub fn request_with_envelope<'a, T, R>(&self, id: &'a str, envelope: Envelope, data: T) -> Result<PendingReply<R>, ClusterError<'a>>
+    where
+        T: Any + Send + 'static,
+        R: Any + Send + 'static,
+    {
+        if let Some(sender) = self.msger_pool.get(id) {
+            return sender.request_with_envelope(envelope, data).map_err(|_| ClusterError::AlreadyDisconnected)
+        }
+
+        Err(ClusterError::IdNotFound(id))
+    }
+
+    pub(crate) fn sender(&self, id: &str) -> Option<&MessageSender> {
+        self.msger_pool.get(id)
+    }
+
+    pub(crate) fn add_unique<T>(&mut self, id: &'static str, obj: T, tx: MessageSender, rx: MessageReceiver) -> Result<(), ClusterError<'_>>
     where
         T: Unit + Send + 'static
     {
-        if self.msger_pool.get(&id).is_none() {
-            let handle = MessageEventHandle::new(obj, rx);
+        if !self.msger_pool.contains_key(&id) {
+            let handle = MessageEventHandle::new(obj, rx, None);
 
             if self.inner_sender.send(ClusterMessage::NewMessageEvent(handle)).is_err() {
                 return Err(ClusterError::RegistrationError)
@@ -106,6 +204,15 @@ impl Cluster {
 
         Err(ClusterError::IdAlreadyUsed(id))
     }
+
+    pub(crate) fn add_supervised(&mut self, id: &'static str, tx: MessageSender) -> Result<Spawner, ClusterError<'static>> {
+        if self.msger_pool.contains_key(&id) {
+            return Err(ClusterError::IdAlreadyUsed(id))
+        }
+
+        self.msger_pool.insert(id, tx);
+        Ok(Spawner(self.inner_sender.clone()))
+    }
 }
 
 impl Drop for Cluster {
@@ -125,29 +232,56 @@ impl Default for Cluster {
 
 /* ---------- */
 
+// Lets a supervisor put a new instance of a unit behind an id it already owns.
+pub(crate) struct Spawner(Sender<ClusterMessage>);
+
+impl Spawner {
+    pub(crate) fn spawn<T: Unit + Send + 'static>(&self, obj: T, rx: MessageReceiver, monitor: Monitor) {
+        let handle = MessageEventHandle::new(obj, rx, Some(monitor));
+
+        if let Err(err) = self.0.send(ClusterMessage::NewMessageEvent(handle)) {
+            if let ClusterMessage::NewMessageEvent(handle) = err.into_inner() {
+                stop(handle, Ok(StopReason::ClusterDropped));
+            }
+        }
+    }
+}
+
+/* ---------- */
+
 struct MessageEventHandle {
     msg_event: Box<dyn Unit + 'static>,
-    rx: MessageReceiver
+    rx: MessageReceiver,
+    monitor: Option<Monitor>
 }
 
 impl MessageEventHandle {
-    fn new<T: Unit + Send + 'static>(obj: T, recv: MessageReceiver) -> Self {
+    fn new<T: Unit + Send + 'static>(obj: T, recv: MessageReceiver, monitor: Option<Monitor>) -> Self {
         Self {
             msg_event: Box::new(obj),
-            rx: recv
+            rx: recv,
+            monitor
         }
     }
 
-    fn recv(&self) -> Result<Message, RecvError> {
-        self.rx.recv_msg()
+    fn try_recv(&self) -> Result<(Envelope, Message), TryRecvError> {
+        self.rx.try_recv_envelope()
+    }
+
+    fn send(&self, to: ReplySender, data: Content) -> Result<(), SendReplyError> {
+        self.rx.send_reply(to, data)
     }
 
-    fn send(&self, data: Content) -> Result<(), SendReplyError> {
-        self.rx.send_reply(data)
+    fn lanes(&self) -> &[Receiver<Letter>] {
+        self.rx.wait_lanes()
     }
 
-    fn inner_recver(&self) -> &Receiver<Message> {
-        self.rx.msg_recver()
+    fn is_idle(&self) -> bool {
+        self.rx.is_empty()
+    }
+
+    fn intercept(&self, data: &Content) -> Option<bool> {
+        self.monitor.as_ref().and_then(|monitor| monitor.intercept(data))
     }
 }
 
@@ -167,7 +301,7 @@ impl DerefMut for MessageEventHandle {
 /* ---------- */
 
 #[derive(Default)]
-struct HandleList(Vec<(usize, MessageEventHandle)>);
+struct HandleList(Vec<(Vec<usize>, MessageEventHandle)>);
 
 impl HandleList {
     fn new() -> Self {
@@ -175,28 +309,41 @@ impl HandleList {
     }
 
     fn register_all<'a>(&'a mut self, sel: &mut Select<'a>) {
-        self.0.iter_mut().for_each(|(idx, handle)| {
-            *idx = sel.recv(handle.inner_recver());
+        self.0.iter_mut().for_each(|(ops, handle)| {
+            *ops = handle.lanes().iter().map(|lane| sel.recv(lane)).collect();
         });
     }
 
-    fn add(&mut self, handle: MessageEventHandle) {
-        self.0.push((0, handle))
+    fn add(&mut self, mut handle: MessageEventHandle) {
+        match panic::catch_unwind(|| handle.on_start()) {
+            Ok(()) => self.0.push((Vec::new(), handle)),
+            Err(msg) => stop(handle, Err(msg))
+        }
     }
 
-    fn remove(&mut self, idx: usize) {
-        if let Some(pos) = self.0.iter().position(|(handle_idx, _)| idx == *handle_idx) {
-            self.0.remove(pos);
+    fn remove(&mut self, idx: usize, reason: Result<StopReason, String>) {
+        if let Some(pos) = self.0.iter().position(|(ops, _)| ops.contains(&idx)) {
+            let (_, handle) = self.0.remove(pos);
+            stop(handle, reason);
         }
     }
 
+    fn stop_all(&mut self, reason: StopReason) {
+        self.0.drain(..).for_each(|(_, handle)| stop(handle, Ok(reason)));
+    }
+
     fn get_handle_mut(&mut self, idx: usize) -> Option<&mut MessageEventHandle> {
         self.0.iter_mut()
-            .find(|(handle_idx, _)| idx == *handle_idx)
+            .find(|(ops, _)| ops.contains(&idx))
             .map(|(_, handle)| handle)
     }
 }
 
+// a unit that panicked takes its queued requests down with it, not the whole cluster
+fn stop(mut handle: MessageEventHandle, reason: Result<StopReason, String>) {
+    unit::stop_unit(&mut *handle.msg_event, handle.rx, reason, handle.monitor)
+}
+
 /* ---------- */
 
 fn group_recv_loop_thread(group_recv: Receiver<ClusterMessage>) {
@@ -212,21 +359,66 @@ fn group_recv_loop_thread(group_recv: Receiver<ClusterMessage>) {
 
         if idx == group_index {
             match group_recv.recv() {
-                Ok(ClusterMessage::Stop) => return,
                 Ok(ClusterMessage::NewMessageEvent(handle)) => {
                     handles.add(handle)
                 }
-                _ => return
+                _ => return handles.stop_all(StopReason::ClusterDropped)
             }
         } else if let Some(handle) = handles.get_handle_mut(idx) {
-            match handle.recv() {
-                Ok(Message::Simple(data)) => handle.on_message(data),
-                Ok(Message::WithReply(data)) => {
-                    let reply = handle.on_message_with_reply(data);
-                    let _ = handle.send(reply);
+            let handled = match handle.try_recv() {
+                Ok((envelope, Message::Simple(data))) => match handle.intercept(&data) {
+                    Some(true) => {
+                        handles.remove(idx, Ok(StopReason::Restart));
+                        continue
+                    }
+                    Some(false) => continue,
+                    None => panic::catch_unwind(|| {
+                        handle.on_envelope(&envelope);
+                        handle.on_message(data)
+                    })
+                },
+                Ok((envelope, Message::WithReply(data, reply_to))) => {
+                    let reply = panic::catch_unwind(|| {
+                        handle.on_envelope(&envelope);
+                        handle.on_message_with_reply(data)
+                    });
+
+                    match reply {
+                        Ok(reply) => {
+                            let _ = handle.send(reply_to, reply);
+                            Ok(())
+                        }
+                        Err(msg) => {
+                            reply_to.fail(ReplyError::UnitPanicked(msg.clone()));
+                            Err(msg)
+                        }
+                    }
+                }
+                Ok((_, Message::Disconnect)) => {
+                    handles.remove(idx, Ok(StopReason::Removed));
+                    continue
+                }
+                Err(TryRecvError::Disconnected) => {
+                    handles.remove(idx, Ok(StopReason::Disconnected));
+                    continue
                 }
-                Ok(Message::Disconnect) => handles.remove(idx),
-                Err(_) => handles.remove(idx)
+                Err(TryRecvError::Empty) => continue
+            };
+
+            let handled = handled.and_then(|_| panic::catch_unwind(|| handle.should_stop()));
+
+            if let Ok(true) = handled {
+                handles.remove(idx, Ok(StopReason::Stopped));
+                continue
+            }
+
+            let handled = handled.and_then(|_| match handle.is_idle() {
+                true => panic::catch_unwind(|| handle.on_idle()),
+                false => Ok(())
+            });
+
+            if let Err(msg) = handled {
+                handles.remove(idx, Err(msg));
             }
         }
     }
@@ -0,0 +1,70 @@
+// Matches a Content against typed arms, trying them in order:
+//
+//     match_content!(msg, {
+//         Spawn<T> => |Spawn(name, arg)| self.new_task(name, arg),
+//         Abort => |Abort(name)| self.abort(name),
+//         _ => {}
+//     })
+//
+// The `reply` form converts the result of every typed arm into a Content,
+// its fallback arm has to produce a Content itself (`_ => |msg| msg` echoes).
+
+#[macro_export]
+macro_rules! match_content {
+    (@arm $c:ident; _ => |$p:pat_param| $fallback:expr $(,)?) => {{
+        let $p = $c;
+        $fallback
+    }};
+
+    (@arm $c:ident; _ => $fallback:expr $(,)?) => {{
+        let _ = $c;
+        $fallback
+    }};
+
+    (@arm $c:ident; $t:ty => |$p:pat_param| $body:expr $(, $($rest:tt)*)?) => {
+        match $c.downcast::<$t>() {
+            Ok($p) => $body,
+            Err($c) => $crate::match_content!(@arm $c; $($($rest)*)?)
+        }
+    };
+
+    (@arm $c:ident;) => {{
+        let _ = $c;
+    }};
+
+    /* ---------- */
+
+    (@reply $c:ident; _ => |$p:pat_param| $fallback:expr $(,)?) => {{
+        let $p = $c;
+        $fallback
+    }};
+
+    (@reply $c:ident; _ => $fallback:expr $(,)?) => {{
+        let _ = $c;
+        $fallback
+    }};
+
+    (@reply $c:ident; $t:ty => |$p:pat_param| $body:expr $(, $($rest:tt)*)?) => {
+        match $c.downcast::<$t>() {
+            Ok($p) => $crate::IntoContent::into_content($body),
+            Err($c) => $crate::match_content!(@reply $c; $($($rest)*)?)
+        }
+    };
+
+    (@reply $c:ident;) => {{
+        let _ = $c;
+        $crate::IntoContent::into_content(())
+    }};
+
+    /* ---------- */
+
+    (reply $content:expr, { $($arms:tt)* }) => {{
+        let content: $crate::Content = $content;
+        $crate::match_content!(@reply content; $($arms)*)
+    }};
+
+    ($content:expr, { $($arms:tt)* }) => {{
+        let content: $crate::Content = $content;
+        $crate::match_content!(@arm content; $($arms)*)
+    }};
+}
@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant, SystemTime};
+
+/* ---------- */
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+#[derive(Debug, Clone)]
+pub struct Envelope {
+    id: u64,
+    timestamp: SystemTime,
+    sender: Option<String>,
+    correlation_id: Option<u64>,
+    deadline: Option<Instant>,
+    headers: HashMap<String, String>
+}
+
+impl Envelope {
+    pub fn new() -> Self {
+        Self {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            timestamp: SystemTime::now(),
+            sender: None,
+            correlation_id: None,
+            deadline: None,
+            headers: HashMap::new()
+        }
+    }
+
+    // keeps the correlation id, deadline and headers so a request can be traced from unit to unit
+    pub fn follow_up(&self) -> Self {
+        Self {
+            correlation_id: Some(self.correlation_id.unwrap_or(self.id)),
+            deadline: self.deadline,
+            headers: self.headers.clone(),
+            ..Self::new()
+        }
+    }
+
+    pub fn with_sender<S: Into<String>>(mut self, sender: S) -> Self {
+        self.sender = Some(sender.into());
+        self
+    }
+
+    pub fn with_correlation_id(mut self, id: u64) -> Self {
+        self.correlation_id = Some(id);
+        self
+    }
+
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    pub fn with_ttl(self, ttl: Duration) -> Self {
+        self.with_deadline(Instant::now() + ttl)
+    }
+
+    pub fn with_header<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        self.headers.insert(key.into(), value.into());
+        self
+    }
+
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn timestamp(&self) -> SystemTime {
+        self.timestamp
+    }
+
+    pub fn sender(&self) -> Option<&str> {
+        self.sender.as_deref()
+    }
+
+    pub fn correlation_id(&self) -> Option<u64> {
+        self.correlation_id
+    }
+
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.deadline.is_some_and(|deadline| deadline <= Instant::now())
+    }
+
+    pub fn header(&self, key: &str) -> Option<&str> {
+        self.headers.get(key).map(String::as_str)
+    }
+
+    pub fn headers(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.headers.iter().map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+}
+
+impl Default for Envelope {
+    fn default() -> Self {
+        Self::new()
+    }
+}
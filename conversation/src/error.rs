@@ -1,18 +1,31 @@
 use std::error::Error;
 use std::fmt::{Display, Debug, Formatter, Result};
 
+use crate::channel::Letter;
 use crate::message::{Content, Message};
 
 /* ---------- */
 
-#[derive(Debug)]
-pub struct ConvertContentError;
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ConvertContentError {
+    pub expected: &'static str,
+    pub found: &'static str
+}
+
+impl ConvertContentError {
+    pub fn new<T: ?Sized>(found: &'static str) -> Self {
+        Self {
+            expected: std::any::type_name::<T>(),
+            found
+        }
+    }
+}
 
 impl Error for ConvertContentError {}
 
 impl Display for ConvertContentError {
     fn fmt(&self, f: &mut Formatter) -> Result {
-        write!(f, "failed to convert Content into given type")
+        write!(f, "failed to convert Content: expected {}, found {}", self.expected, self.found)
     }
 }
 
@@ -25,7 +38,7 @@ impl Error for RecvError {}
 
 impl Display for RecvError {
     fn fmt(&self, f: &mut Formatter) -> Result {
-        write!(f, "failed to recv: channel empty or disconnected")
+        write!(f, "failed to recv: channel disconnected")
     }
 }
 
@@ -37,12 +50,96 @@ impl From<crossbeam_channel::RecvError> for RecvError {
 
 /* ---------- */
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum TryRecvError {
+    Empty,
+    Disconnected
+}
+
+impl TryRecvError {
+    pub fn is_empty(&self) -> bool {
+        matches!(self, Self::Empty)
+    }
+
+    pub fn is_disconnected(&self) -> bool {
+        matches!(self, Self::Disconnected)
+    }
+}
+
+impl Error for TryRecvError {}
+
+impl Display for TryRecvError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::Empty => write!(f, "failed to recv: channel empty"),
+            Self::Disconnected => write!(f, "failed to recv: channel disconnected")
+        }
+    }
+}
+
+impl From<crossbeam_channel::TryRecvError> for TryRecvError {
+    fn from(err: crossbeam_channel::TryRecvError) -> Self {
+        match err {
+            crossbeam_channel::TryRecvError::Empty => Self::Empty,
+            crossbeam_channel::TryRecvError::Disconnected => Self::Disconnected
+        }
+    }
+}
+
+/* ---------- */
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum RecvTimeoutError {
+    Timeout,
+    Disconnected
+}
+
+impl RecvTimeoutError {
+    pub fn is_timeout(&self) -> bool {
+        matches!(self, Self::Timeout)
+    }
+
+    pub fn is_disconnected(&self) -> bool {
+        matches!(self, Self::Disconnected)
+    }
+}
+
+impl Error for RecvTimeoutError {}
+
+impl Display for RecvTimeoutError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::Timeout => write!(f, "failed to recv: timed out"),
+            Self::Disconnected => write!(f, "failed to recv: channel disconnected")
+        }
+    }
+}
+
+impl From<crossbeam_channel::RecvTimeoutError> for RecvTimeoutError {
+    fn from(err: crossbeam_channel::RecvTimeoutError) -> Self {
+        match err {
+            crossbeam_channel::RecvTimeoutError::Timeout => Self::Timeout,
+            crossbeam_channel::RecvTimeoutError::Disconnected => Self::Disconnected
+        }
+    }
+}
+
+/* ---------- */
+
 pub struct SendMessageError(Message);
 
 impl SendMessageError {
     pub fn into<T: 'static>(self) -> Option<T> {
         self.0.into()
     }
+
+    pub fn downcast<T: 'static>(self) -> std::result::Result<T, Self> {
+        self.0.downcast().map_err(Self)
+    }
+
+    pub fn into_inner(self) -> Message {
+        self.0
+    }
 }
 
 impl Error for SendMessageError {}
@@ -59,9 +156,127 @@ impl Debug for SendMessageError {
     }
 }
 
-impl From<crossbeam_channel::SendError<Message>> for SendMessageError {
-    fn from(err: crossbeam_channel::SendError<Message>) -> Self {
-        Self(err.into_inner())
+impl From<crossbeam_channel::SendError<Letter>> for SendMessageError {
+    fn from(err: crossbeam_channel::SendError<Letter>) -> Self {
+        Self(err.into_inner().1)
+    }
+}
+
+/* ---------- */
+
+pub enum TrySendMessageError {
+    Full(Message),
+    Disconnected(Message)
+}
+
+impl TrySendMessageError {
+    pub fn into<T: 'static>(self) -> Option<T> {
+        match self {
+            Self::Full(msg) | Self::Disconnected(msg) => msg.into()
+        }
+    }
+
+    pub fn downcast<T: 'static>(self) -> std::result::Result<T, Self> {
+        match self {
+            Self::Full(msg) => msg.downcast().map_err(Self::Full),
+            Self::Disconnected(msg) => msg.downcast().map_err(Self::Disconnected)
+        }
+    }
+
+    pub fn is_full(&self) -> bool {
+        matches!(self, Self::Full(_))
+    }
+
+    pub fn is_disconnected(&self) -> bool {
+        matches!(self, Self::Disconnected(_))
+    }
+}
+
+impl Error for TrySendMessageError {}
+
+impl Display for TrySendMessageError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::Full(_) => write!(f, "failed to send: channel full"),
+            Self::Disconnected(_) => write!(f, "failed to send: channel disconnected")
+        }
+    }
+}
+
+impl Debug for TrySendMessageError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::Full(_) => write!(f, "Full(...)"),
+            Self::Disconnected(_) => write!(f, "Disconnected(...)")
+        }
+    }
+}
+
+impl From<crossbeam_channel::TrySendError<Letter>> for TrySendMessageError {
+    fn from(err: crossbeam_channel::TrySendError<Letter>) -> Self {
+        match err {
+            crossbeam_channel::TrySendError::Full((_, msg)) => Self::Full(msg),
+            crossbeam_channel::TrySendError::Disconnected((_, msg)) => Self::Disconnected(msg)
+        }
+    }
+}
+
+/* ---------- */
+
+pub enum SendMessageTimeoutError {
+    Timeout(Message),
+    Disconnected(Message)
+}
+
+impl SendMessageTimeoutError {
+    pub fn into<T: 'static>(self) -> Option<T> {
+        match self {
+            Self::Timeout(msg) | Self::Disconnected(msg) => msg.into()
+        }
+    }
+
+    pub fn downcast<T: 'static>(self) -> std::result::Result<T, Self> {
+        match self {
+            Self::Timeout(msg) => msg.downcast().map_err(Self::Timeout),
+            Self::Disconnected(msg) => msg.downcast().map_err(Self::Disconnected)
+        }
+    }
+
+    pub fn is_timeout(&self) -> bool {
+        matches!(self, Self::Timeout(_))
+    }
+
+    pub fn is_disconnected(&self) -> bool {
+        matches!(self, Self::Disconnected(_))
+    }
+}
+
+impl Error for SendMessageTimeoutError {}
+
+impl Display for SendMessageTimeoutError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::Timeout(_) => write!(f, "failed to send: timed out on full channel"),
+            Self::Disconnected(_) => write!(f, "failed to send: channel disconnected")
+        }
+    }
+}
+
+impl Debug for SendMessageTimeoutError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::Timeout(_) => write!(f, "Timeout(...)"),
+            Self::Disconnected(_) => write!(f, "Disconnected(...)")
+        }
+    }
+}
+
+impl From<crossbeam_channel::SendTimeoutError<Letter>> for SendMessageTimeoutError {
+    fn from(err: crossbeam_channel::SendTimeoutError<Letter>) -> Self {
+        match err {
+            crossbeam_channel::SendTimeoutError::Timeout((_, msg)) => Self::Timeout(msg),
+            crossbeam_channel::SendTimeoutError::Disconnected((_, msg)) => Self::Disconnected(msg)
+        }
     }
 }
 
@@ -73,6 +288,14 @@ impl SendReplyError {
     pub fn into<T: 'static>(self) -> Option<T> {
         self.0.into()
     }
+
+    pub fn downcast<T: 'static>(self) -> std::result::Result<T, Self> {
+        self.0.downcast().map_err(Self)
+    }
+
+    pub fn into_inner(self) -> Content {
+        self.0
+    }
 }
 
 impl Error for SendReplyError {}
@@ -100,7 +323,9 @@ impl From<crossbeam_channel::SendError<Content>> for SendReplyError {
 pub enum SendMessageWithReplyError {
     SendError(Message),
     RecvError,
-    ConvertContentError
+    Expired,
+    UnitPanicked(String),
+    ConvertContentError(ConvertContentError)
 }
 
 impl SendMessageWithReplyError {
@@ -119,7 +344,9 @@ impl Display for SendMessageWithReplyError {
         match self {
             Self::SendError(_) => write!(f, "failed to send: channel disconnected"),
             Self::RecvError => write!(f, "failed to recv reply: channel disconnected"),
-            Self::ConvertContentError => write!(f, "failed to convert Content into given type")
+            Self::Expired => write!(f, "failed to recv reply: message expired"),
+            Self::UnitPanicked(msg) => write!(f, "failed to recv reply: unit panicked: {}", msg),
+            Self::ConvertContentError(err) => write!(f, "{}", err)
         }
     }
 }
@@ -129,7 +356,9 @@ impl Debug for SendMessageWithReplyError {
         match self {
             Self::SendError(_) => write!(f, "SendError(...)"),
             Self::RecvError => write!(f, "RecvError"),
-            Self::ConvertContentError => write!(f, "ConvertContentError")
+            Self::Expired => write!(f, "Expired"),
+            Self::UnitPanicked(msg) => write!(f, "UnitPanicked({:?})", msg),
+            Self::ConvertContentError(err) => write!(f, "ConvertContentError({:?})", err)
         }
     }
 }
@@ -147,8 +376,127 @@ impl From<RecvError> for SendMessageWithReplyError {
 }
 
 impl From<ConvertContentError> for SendMessageWithReplyError {
-    fn from(_: ConvertContentError) -> Self {
-        Self::ConvertContentError
+    fn from(err: ConvertContentError) -> Self {
+        Self::ConvertContentError(err)
+    }
+}
+
+impl From<ReplyError> for SendMessageWithReplyError {
+    fn from(err: ReplyError) -> Self {
+        match err {
+            ReplyError::ConvertContentError(err) => Self::ConvertContentError(err),
+            ReplyError::Expired => Self::Expired,
+            ReplyError::UnitPanicked(msg) => Self::UnitPanicked(msg),
+            _ => Self::RecvError
+        }
+    }
+}
+
+/* ---------- */
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ReplyError {
+    NotReady,
+    Timeout,
+    Disconnected,
+    Expired,
+    UnitPanicked(String),
+    ConvertContentError(ConvertContentError)
+}
+
+impl Error for ReplyError {}
+
+impl Display for ReplyError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::NotReady => write!(f, "reply not ready yet"),
+            Self::Timeout => write!(f, "failed to recv reply: timed out"),
+            Self::Disconnected => write!(f, "failed to recv reply: channel disconnected"),
+            Self::Expired => write!(f, "failed to recv reply: message expired"),
+            Self::UnitPanicked(msg) => write!(f, "failed to recv reply: unit panicked: {}", msg),
+            Self::ConvertContentError(err) => write!(f, "{}", err)
+        }
+    }
+}
+
+impl From<RecvError> for ReplyError {
+    fn from(_: RecvError) -> Self {
+        Self::Disconnected
+    }
+}
+
+impl From<TryRecvError> for ReplyError {
+    fn from(err: TryRecvError) -> Self {
+        match err {
+            TryRecvError::Empty => Self::NotReady,
+            TryRecvError::Disconnected => Self::Disconnected
+        }
+    }
+}
+
+impl From<RecvTimeoutError> for ReplyError {
+    fn from(err: RecvTimeoutError) -> Self {
+        match err {
+            RecvTimeoutError::Timeout => Self::Timeout,
+            RecvTimeoutError::Disconnected => Self::Disconnected
+        }
+    }
+}
+
+impl From<ConvertContentError> for ReplyError {
+    fn from(err: ConvertContentError) -> Self {
+        Self::ConvertContentError(err)
+    }
+}
+
+/* ---------- */
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct TrySelectError;
+
+impl Error for TrySelectError {}
+
+impl Display for TrySelectError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "no operation ready")
+    }
+}
+
+impl From<crossbeam_channel::TryReadyError> for TrySelectError {
+    fn from(_: crossbeam_channel::TryReadyError) -> Self {
+        Self
+    }
+}
+
+/* ---------- */
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct SelectTimeoutError;
+
+impl Error for SelectTimeoutError {}
+
+impl Display for SelectTimeoutError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "timed out waiting for an operation to become ready")
+    }
+}
+
+impl From<crossbeam_channel::ReadyTimeoutError> for SelectTimeoutError {
+    fn from(_: crossbeam_channel::ReadyTimeoutError) -> Self {
+        Self
+    }
+}
+
+/* ---------- */
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct RegisterTypeError(pub &'static str);
+
+impl Error for RegisterTypeError {}
+
+impl Display for RegisterTypeError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "failed to register type: tag {} already used by another type", self.0)
     }
 }
 
@@ -157,8 +505,10 @@ impl From<ConvertContentError> for SendMessageWithReplyError {
 pub enum ClusterError<'a> {
     RegistrationError,
     UnsetIdError,
-    ContentConversionError,
+    ContentConversionError(ConvertContentError),
     AlreadyDisconnected,
+    Expired,
+    UnitPanicked(String),
     IdAlreadyUsed(&'a str),
     IdNotFound(&'a str)
 }
@@ -170,8 +520,10 @@ impl Display for ClusterError<'_> {
         match self {
             Self::RegistrationError => write!(f, "failed to register to cluster"),
             Self::UnsetIdError => write!(f, "error: id not set"),
-            Self::ContentConversionError => write!(f, "failed to perform conversion"),
+            Self::ContentConversionError(err) => write!(f, "{}", err),
             Self::AlreadyDisconnected => write!(f, "already disconnected"),
+            Self::Expired => write!(f, "message expired"),
+            Self::UnitPanicked(msg) => write!(f, "unit panicked: {}", msg),
             Self::IdAlreadyUsed(id) => write!(f, "id {} already in used", id),
             Self::IdNotFound(id) => write!(f, "id {} not found", id)
         }
@@ -183,10 +535,87 @@ impl Debug for ClusterError<'_> {
         match self {
             Self::RegistrationError => write!(f, "RegistrationError"),
             Self::UnsetIdError => write!(f, "UnsetIdError"),
-            Self::ContentConversionError => write!(f, "ContentConversionError"),
+            Self::ContentConversionError(err) => write!(f, "ContentConversionError({:?})", err),
             Self::AlreadyDisconnected => write!(f, "Disconnected"),
+            Self::Expired => write!(f, "Expired"),
+            Self::UnitPanicked(msg) => write!(f, "UnitPanicked({:?})", msg),
             Self::IdAlreadyUsed(id) => write!(f, "IdAlreadyUsed({:?})", id),
             Self::IdNotFound(id) => write!(f, "IdNotFound({:?})", id)
         }
     }
 }
+
+/* ---------- */
+
+pub enum SupervisorError {
+    NameAlreadyUsed(&'static str),
+    Stopped,
+    ClusterError(ClusterError<'static>)
+}
+
+impl Error for SupervisorError {}
+
+impl Display for SupervisorError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::NameAlreadyUsed(name) => write!(f, "name {} already in use", name),
+            Self::Stopped => write!(f, "supervisor stopped"),
+            Self::ClusterError(err) => write!(f, "{}", err)
+        }
+    }
+}
+
+impl Debug for SupervisorError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::NameAlreadyUsed(name) => write!(f, "NameAlreadyUsed({:?})", name),
+            Self::Stopped => write!(f, "Stopped"),
+            Self::ClusterError(err) => write!(f, "ClusterError({:?})", err)
+        }
+    }
+}
+
+/* ---------- */
+
+pub enum TryReplyError<E> {
+    Failed(E),
+    Reply(SendMessageWithReplyError)
+}
+
+impl<E: Debug> Error for TryReplyError<E> {}
+
+impl<E: Debug> Display for TryReplyError<E> {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::Failed(err) => write!(f, "unit failed: {:?}", err),
+            Self::Reply(err) => write!(f, "{}", err)
+        }
+    }
+}
+
+impl<E: Debug> Debug for TryReplyError<E> {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::Failed(err) => write!(f, "Failed({:?})", err),
+            Self::Reply(err) => write!(f, "Reply({:?})", err)
+        }
+    }
+}
+
+impl<E> From<SendMessageError> for TryReplyError<E> {
+    fn from(err: SendMessageError) -> Self {
+        Self::Reply(SendMessageWithReplyError::from(err))
+    }
+}
+
+impl<E> From<ReplyError> for TryReplyError<E> {
+    fn from(err: ReplyError) -> Self {
+        Self::Reply(SendMessageWithReplyError::from(err))
+    }
+}
+
+impl<E> From<ConvertContentError> for TryReplyError<E> {
+    fn from(err: ConvertContentError) -> Self {
+        Self::Reply(SendMessageWithReplyError::from(err))
+    }
+}
@@ -0,0 +1,72 @@
+pub trait Handler<M>: Send + 'static {
+    type Reply: Send + 'static;
+
+    fn handle(&mut self, msg: M) -> Self::Reply;
+}
+
+/* ---------- */
+
+// Generates the Unit impl of a type from its Handler impls:
+//
+//     handlers!(Scheduler: Spawn, Abort);
+//     handlers!(Scheduler: Spawn; requests: Query; fallback = Self::unknown);
+//     handlers!(Scheduler: Spawn; hooks {
+//         fn on_start(&mut self) { self.open() }
+//     });
+//
+// The types listed under `requests` must reply with their Request::Response,
+// which is checked at compile time. The fallback gets every other content as
+// `fn(&mut Self, Content) -> Content`, without one they are dropped and a
+// with-reply message gets `()` back. The items of `hooks` go into the Unit
+// impl as they are, for the lifecycle and envelope methods.
+
+#[macro_export]
+macro_rules! handlers {
+    (@impl $unit:ty; [$($msg:ty),*]; [$($req:ty),*]; $fallback:expr; { $($hooks:tt)* }) => {
+        impl $crate::Unit for $unit {
+            fn on_message(&mut self, data: $crate::Content) {
+                $crate::match_content!(data, {
+                    $($msg => |msg| {
+                        let _ = <Self as $crate::Handler<$msg>>::handle(self, msg);
+                    },)*
+                    $($req => |msg| {
+                        let _ = <Self as $crate::Handler<$req>>::handle(self, msg);
+                    },)*
+                    _ => |data| {
+                        let _ = ($fallback)(self, data);
+                    }
+                })
+            }
+
+            fn on_message_with_reply(&mut self, data: $crate::Content) -> $crate::Content {
+                $crate::match_content!(reply data, {
+                    $($msg => |msg| <Self as $crate::Handler<$msg>>::handle(self, msg),)*
+                    $($req => |msg| <Self as $crate::Handler<$req>>::handle(self, msg),)*
+                    _ => |data| ($fallback)(self, data)
+                })
+            }
+
+            $($hooks)*
+        }
+
+        const _: () = {
+            fn request<U, Q>()
+            where
+                Q: $crate::Request,
+                U: $crate::Handler<Q, Reply = Q::Response>
+            {}
+
+            let _: &[fn()] = &[$(request::<$unit, $req>),*];
+        };
+    };
+
+    ($unit:ty $(: $($msg:ty),+)? $(; requests: $($req:ty),+)? $(; hooks { $($hooks:tt)* })? $(;)?) => {
+        $crate::handlers!(@impl $unit; [$($($msg),+)?]; [$($($req),+)?]; |_: &mut $unit, _: $crate::Content| {
+            $crate::IntoContent::into_content(())
+        }; { $($($hooks)*)? });
+    };
+
+    ($unit:ty $(: $($msg:ty),+)? $(; requests: $($req:ty),+)?; fallback = $fallback:expr $(; hooks { $($hooks:tt)* })? $(;)?) => {
+        $crate::handlers!(@impl $unit; [$($($msg),+)?]; [$($($req),+)?]; $fallback; { $($($hooks)*)? });
+    };
+}
@@ -0,0 +1,104 @@
+use std::any::{Any, TypeId};
+use std::cell::Cell;
+use std::marker::PhantomData;
+use std::mem::{self, ManuallyDrop, MaybeUninit};
+use std::ptr;
+
+/* ---------- */
+
+const INLINE_WORDS: usize = 3;
+
+type Buffer = MaybeUninit<[usize; INLINE_WORDS]>;
+
+struct VTable {
+    type_id: TypeId,
+    as_any: unsafe fn(&Buffer) -> &(dyn Any + Send + 'static),
+    drop: unsafe fn(&mut Buffer)
+}
+
+struct TypedVTable<T>(PhantomData<T>);
+
+impl<T: Any + Send + 'static> TypedVTable<T> {
+    const VTABLE: VTable = VTable {
+        type_id: TypeId::of::<T>(),
+        as_any: as_any::<T>,
+        drop: drop_in_place::<T>
+    };
+}
+
+unsafe fn as_any<T: Any + Send + 'static>(buf: &Buffer) -> &(dyn Any + Send + 'static) {
+    &*(buf.as_ptr() as *const T)
+}
+
+unsafe fn drop_in_place<T>(buf: &mut Buffer) {
+    ptr::drop_in_place(buf.as_mut_ptr() as *mut T)
+}
+
+/* ---------- */
+
+// Small values are stored next to their vtable instead of behind a Box,
+// sending a number or a unit struct does not allocate.
+pub(crate) struct Inline {
+    buf: Buffer,
+    vtable: &'static VTable,
+    _not_sync: PhantomData<Cell<()>>
+}
+
+impl Inline {
+    pub(crate) const fn fits<T>() -> bool {
+        mem::size_of::<T>() <= mem::size_of::<Buffer>() && mem::align_of::<T>() <= mem::align_of::<Buffer>()
+    }
+
+    pub(crate) fn new<T: Any + Send + 'static>(obj: T) -> Result<Self, T> {
+        if !Self::fits::<T>() {
+            return Err(obj)
+        }
+
+        let mut buf = Buffer::uninit();
+
+        // SAFETY: size and alignment of T were checked against the buffer above
+        unsafe { ptr::write(buf.as_mut_ptr() as *mut T, obj) };
+
+        Ok(Self {
+            buf,
+            vtable: &TypedVTable::<T>::VTABLE,
+            _not_sync: PhantomData
+        })
+    }
+
+    pub(crate) fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        // SAFETY: the vtable was built for the type written in the buffer
+        unsafe { (self.vtable.as_any)(&self.buf) }
+    }
+
+    pub(crate) fn is<T: 'static>(&self) -> bool {
+        self.vtable.type_id == TypeId::of::<T>()
+    }
+
+    pub(crate) fn downcast_ref<T: 'static>(&self) -> Option<&T> {
+        if !self.is::<T>() {
+            return None
+        }
+
+        // SAFETY: the buffer holds a T, checked above
+        Some(unsafe { &*(self.buf.as_ptr() as *const T) })
+    }
+
+    pub(crate) fn take<T: 'static>(self) -> Result<T, Self> {
+        if !self.is::<T>() {
+            return Err(self)
+        }
+
+        let this = ManuallyDrop::new(self);
+
+        // SAFETY: the buffer holds a T and is never read or dropped again
+        Ok(unsafe { ptr::read(this.buf.as_ptr() as *const T) })
+    }
+}
+
+impl Drop for Inline {
+    fn drop(&mut self) {
+        // SAFETY: the buffer still holds the value the vtable was built for
+        unsafe { (self.vtable.drop)(&mut self.buf) }
+    }
+}
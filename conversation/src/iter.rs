@@ -0,0 +1,189 @@
+use std::any::Any;
+use std::marker::PhantomData;
+
+use crate::channel::MessageReceiver;
+use crate::message::Message;
+
+/* ---------- */
+
+fn next_msg(recv: &MessageReceiver, done: &mut bool) -> Option<Message> {
+    if *done {
+        return None
+    }
+
+    match recv.recv_msg() {
+        Ok(Message::Disconnect) | Err(_) => {
+            *done = true;
+            None
+        }
+        Ok(msg) => Some(msg)
+    }
+}
+
+/* ---------- */
+
+pub struct Iter<'a> {
+    recv: &'a MessageReceiver,
+    done: bool
+}
+
+impl<'a> Iter<'a> {
+    pub(crate) fn new(recv: &'a MessageReceiver) -> Self {
+        Self {
+            recv,
+            done: false
+        }
+    }
+}
+
+impl Iterator for Iter<'_> {
+    type Item = Message;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        next_msg(self.recv, &mut self.done)
+    }
+}
+
+/* ---------- */
+
+pub struct TryIter<'a> {
+    recv: &'a MessageReceiver,
+    done: bool
+}
+
+impl<'a> TryIter<'a> {
+    pub(crate) fn new(recv: &'a MessageReceiver) -> Self {
+        Self {
+            recv,
+            done: false
+        }
+    }
+}
+
+impl Iterator for TryIter<'_> {
+    type Item = Message;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None
+        }
+
+        match self.recv.try_recv_msg() {
+            Ok(Message::Disconnect) => {
+                self.done = true;
+                None
+            }
+            Ok(msg) => Some(msg),
+            Err(_) => None
+        }
+    }
+}
+
+/* ---------- */
+
+pub struct IntoIter {
+    recv: MessageReceiver,
+    done: bool
+}
+
+impl Iterator for IntoIter {
+    type Item = Message;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        next_msg(&self.recv, &mut self.done)
+    }
+}
+
+impl IntoIterator for MessageReceiver {
+    type Item = Message;
+    type IntoIter = IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            recv: self,
+            done: false
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a MessageReceiver {
+    type Item = Message;
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Iter::new(self)
+    }
+}
+
+/* ---------- */
+
+pub struct IterOf<'a, T> {
+    inner: Iter<'a>,
+    _type: PhantomData<fn() -> T>
+}
+
+impl<'a, T> IterOf<'a, T> {
+    pub(crate) fn new(recv: &'a MessageReceiver) -> Self {
+        Self {
+            inner: Iter::new(recv),
+            _type: PhantomData
+        }
+    }
+}
+
+impl<T: Any + Send + 'static> Iterator for IterOf<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.by_ref().find_map(|msg| msg.into::<T>())
+    }
+}
+
+/* ---------- */
+
+#[cfg(feature = "stream")]
+pub use self::stream::MessageStream;
+
+#[cfg(feature = "stream")]
+mod stream {
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    use futures_core::Stream;
+
+    use crate::channel::MessageReceiver;
+    use crate::message::Message;
+
+    pub struct MessageStream {
+        recv: MessageReceiver,
+        done: bool
+    }
+
+    impl MessageStream {
+        pub(crate) fn new(recv: MessageReceiver) -> Self {
+            Self {
+                recv,
+                done: false
+            }
+        }
+    }
+
+    impl Stream for MessageStream {
+        type Item = Message;
+
+        fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            if self.done {
+                return Poll::Ready(None)
+            }
+
+            match self.recv.poll_recv_msg(cx) {
+                Poll::Ready(Ok(Message::Disconnect)) | Poll::Ready(Err(_)) => {
+                    self.done = true;
+                    Poll::Ready(None)
+                }
+                Poll::Ready(Ok(msg)) => Poll::Ready(Some(msg)),
+                Poll::Pending => Poll::Pending
+            }
+        }
+    }
+}
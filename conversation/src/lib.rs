@@ -1,11 +1,47 @@
+mod broadcast;
 mod channel;
 mod error;
 mod cluster;
+mod dispatch;
+mod envelope;
+mod handler;
+mod inline;
+mod iter;
 mod message;
+mod panic;
+mod request;
+mod select;
+mod signal;
+mod stats;
+mod supervisor;
+mod try_unit;
+mod typed;
 mod unit;
 
+#[cfg(feature = "serde")]
+mod serial;
+
+#[cfg(feature = "tokio")]
+mod tokio_compat;
+
 pub use crate::error::*;
+pub use crate::broadcast::{broadcast, Publisher};
 pub use crate::cluster::Cluster;
+pub use crate::envelope::Envelope;
+pub use crate::handler::Handler;
 pub use crate::message::{Content, IntoContent, Message, IntoMessage};
-pub use crate::unit::{Unit, WithContext, Pipe, Builder};
+pub use crate::request::Request;
+pub use crate::select::Select;
+pub use crate::stats::ChannelStats;
+pub use crate::supervisor::{Supervisor, Strategy};
+pub use crate::try_unit::{TryUnit, Fallible, ErrorPolicy, TrySender, TryPipe};
+pub use crate::typed::{TypedUnit, Typed, TypedSender, TypedReceiver, TypedMessage, TypedReplySender, TypedPipe, typed_channel};
+pub use crate::unit::{Unit, StopReason, WithContext, Pipe, Builder};
 pub use channel::*;
+pub use crate::iter::{Iter, TryIter, IntoIter, IterOf};
+
+#[cfg(feature = "stream")]
+pub use crate::iter::MessageStream;
+
+#[cfg(feature = "serde")]
+pub use crate::serial::{register_type, is_registered};
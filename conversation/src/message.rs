@@ -1,27 +1,222 @@
 use std::any::Any;
+use std::fmt::{Debug, Formatter, Result};
+use std::sync::Arc;
+
+use crate::ConvertContentError;
+use crate::inline::Inline;
+use crate::channel::{self, ReplySender, ReplyReceiver};
 
 /* ---------- */
 
-pub struct Content(Box<dyn Any + Send + 'static>);
+trait SharedPayload: Send + 'static {
+    fn as_any(&self) -> &(dyn Any + Send + 'static);
+    fn share(&self) -> Box<dyn SharedPayload>;
+    fn try_take(self: Box<Self>) -> std::result::Result<Box<dyn Any + Send>, Box<dyn SharedPayload>>;
+    fn try_clone(&self) -> Option<Box<dyn Any + Send>>;
+    fn into_arc(self: Box<Self>) -> Box<dyn Any + Send>;
+}
+
+// an Arc plus, when the value is Clone, the way to get an owned copy out of it
+struct Shared<T> {
+    arc: Arc<T>,
+    clone: Option<fn(&T) -> T>
+}
+
+impl<T: Any + Send + Sync + 'static> SharedPayload for Shared<T> {
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        &*self.arc
+    }
+
+    fn share(&self) -> Box<dyn SharedPayload> {
+        Box::new(Shared {
+            arc: Arc::clone(&self.arc),
+            clone: self.clone
+        })
+    }
+
+    fn try_take(self: Box<Self>) -> std::result::Result<Box<dyn Any + Send>, Box<dyn SharedPayload>> {
+        let clone = self.clone;
+
+        match Arc::try_unwrap(self.arc) {
+            Ok(obj) => Ok(Box::new(obj)),
+            Err(arc) => Err(Box::new(Shared { arc, clone }))
+        }
+    }
+
+    fn try_clone(&self) -> Option<Box<dyn Any + Send>> {
+        self.clone.map(|clone| Box::new(clone(&self.arc)) as Box<dyn Any + Send>)
+    }
+
+    fn into_arc(self: Box<Self>) -> Box<dyn Any + Send> {
+        Box::new(self.arc)
+    }
+}
+
+enum Payload {
+    Inline(Inline),
+    Owned(Box<dyn Any + Send + 'static>),
+    Shared(Box<dyn SharedPayload>)
+}
+
+pub struct Content {
+    inner: Payload,
+    type_name: &'static str
+}
 
 impl Content {
+    // A Content passed in comes back as it is instead of being wrapped a second
+    // time, so sending or replying with one (a shared one too) hands over its
+    // value and no Content ever holds a Content: `is::<Content>()` is always false.
     pub fn from<T: Any + Send + 'static>(obj: T) -> Self {
-        Content(Box::new(obj))
+        let obj = match Inline::new(obj) {
+            Ok(inline) => return Content {
+                inner: Payload::Inline(inline),
+                type_name: std::any::type_name::<T>()
+            },
+            Err(obj) => obj
+        };
+
+        let inner: Box<dyn Any + Send + 'static> = Box::new(obj);
+
+        match inner.downcast::<Content>() {
+            Ok(cont) => *cont,
+            Err(inner) => Content {
+                inner: Payload::Owned(inner),
+                type_name: std::any::type_name::<T>()
+            }
+        }
     }
 
-    pub fn into<T: 'static>(self) -> Option<T> {
-        match self.0.downcast::<T>() {
-            Ok(boxed) => Some(*boxed),
+    // into() on a copy that is still shared gets a clone of the value
+    pub fn shared<T: Clone + Any + Send + Sync + 'static>(obj: T) -> Self {
+        Self::from_shared(Arc::new(obj), Some(T::clone))
+    }
+
+    // the value can't be cloned, only the last holder can take it out
+    pub fn from_arc<T: Any + Send + Sync + 'static>(obj: Arc<T>) -> Self {
+        Self::from_shared(obj, None)
+    }
+
+    fn from_shared<T: Any + Send + Sync + 'static>(arc: Arc<T>, clone: Option<fn(&T) -> T>) -> Self {
+        Content {
+            inner: Payload::Shared(Box::new(Shared { arc, clone })),
+            type_name: std::any::type_name::<T>()
+        }
+    }
+
+    pub fn share(&self) -> Option<Content> {
+        match &self.inner {
+            Payload::Shared(inner) => Some(Content {
+                inner: Payload::Shared(inner.share()),
+                type_name: self.type_name
+            }),
             _ => None
         }
     }
 
+    pub fn is_shared(&self) -> bool {
+        matches!(self.inner, Payload::Shared(_))
+    }
+
+    pub fn into<T: 'static>(self) -> Option<T> {
+        self.downcast().ok()
+    }
+
+    pub fn downcast<T: 'static>(self) -> std::result::Result<T, Content> {
+        if !self.is::<T>() {
+            return Err(self)
+        }
+
+        self.take(true)
+    }
+
+    fn take<T: 'static>(self, clone_shared: bool) -> std::result::Result<T, Content> {
+        let type_name = self.type_name;
+
+        let inner = match self.inner {
+            Payload::Inline(inline) => return inline.take::<T>().map_err(|inline| Content {
+                inner: Payload::Inline(inline),
+                type_name
+            }),
+            Payload::Owned(inner) => inner,
+            Payload::Shared(inner) => match inner.try_take() {
+                Ok(inner) => inner,
+                Err(inner) => match clone_shared.then(|| inner.try_clone()).flatten() {
+                    Some(copy) => copy,
+                    None => return Err(Content {
+                        inner: Payload::Shared(inner),
+                        type_name
+                    })
+                }
+            }
+        };
+
+        match inner.downcast::<T>() {
+            Ok(boxed) => Ok(*boxed),
+            Err(inner) => Err(Content {
+                inner: Payload::Owned(inner),
+                type_name
+            })
+        }
+    }
+
+    // hands the value back to the last holder of a shared content
+    pub fn try_unwrap<T: 'static>(self) -> std::result::Result<T, Content> {
+        if !self.is::<T>() {
+            return Err(self)
+        }
+
+        self.take(false)
+    }
+
+    pub fn into_arc<T: Any + Send + Sync + 'static>(self) -> std::result::Result<Arc<T>, Content> {
+        if !self.is::<T>() {
+            return Err(self)
+        }
+
+        let arc = match self.inner {
+            Payload::Inline(inline) => inline.take::<T>().map(Arc::new).ok(),
+            Payload::Owned(inner) => inner.downcast::<T>().map(|boxed| Arc::new(*boxed)).ok(),
+            Payload::Shared(inner) => inner.into_arc().downcast::<Arc<T>>().map(|arc| *arc).ok()
+        };
+
+        Ok(arc.expect("content type checked above"))
+    }
+
     pub fn as_ref<T: 'static>(&self) -> Option<&T> {
-        self.0.downcast_ref::<T>()
+        match &self.inner {
+            Payload::Inline(inline) => inline.downcast_ref::<T>(),
+            _ => self.inner().downcast_ref::<T>()
+        }
     }
 
     pub fn is<T: 'static>(&self) -> bool {
-        std::any::TypeId::of::<T>() == (*self.0).type_id()
+        match &self.inner {
+            Payload::Inline(inline) => inline.is::<T>(),
+            _ => std::any::TypeId::of::<T>() == self.inner().type_id()
+        }
+    }
+
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    pub(crate) fn inner(&self) -> &(dyn Any + Send + 'static) {
+        match &self.inner {
+            Payload::Inline(inline) => inline.as_any(),
+            Payload::Owned(inner) => &**inner,
+            Payload::Shared(inner) => inner.as_any()
+        }
+    }
+
+    pub(crate) fn convert<T: 'static>(self) -> std::result::Result<T, ConvertContentError> {
+        self.downcast::<T>().map_err(|cont| ConvertContentError::new::<T>(cont.type_name))
+    }
+}
+
+impl Debug for Content {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "Content({})", self.type_name)
     }
 }
 
@@ -41,31 +236,56 @@ impl<T: Any + Send + 'static> IntoContent for T {
 
 pub enum Message {
     Simple(Content),
-    WithReply(Content),
+    WithReply(Content, ReplySender),
     Disconnect
 }
 
 impl Message {
     pub fn into<T: 'static>(self) -> Option<T> {
         match self {
-            Self::Simple(cont) | Self::WithReply(cont) => cont.into(),
+            Self::Simple(cont) | Self::WithReply(cont, _) => cont.into(),
             _ => None
         }
     }
 
+    pub fn downcast<T: 'static>(self) -> std::result::Result<T, Message> {
+        match self {
+            Self::Simple(cont) => cont.downcast().map_err(Self::Simple),
+            Self::WithReply(cont, reply_to) => cont.downcast().map_err(|cont| Self::WithReply(cont, reply_to)),
+            Self::Disconnect => Err(Self::Disconnect)
+        }
+    }
+
     pub fn as_ref<T: 'static>(&self) -> Option<&T> {
         match self {
-            Self::Simple(cont) | Self::WithReply(cont) => cont.as_ref(),
+            Self::Simple(cont) | Self::WithReply(cont, _) => cont.as_ref(),
             _ => None
         }
     }
+
+    pub fn type_name(&self) -> Option<&'static str> {
+        match self {
+            Self::Simple(cont) | Self::WithReply(cont, _) => Some(cont.type_name()),
+            _ => None
+        }
+    }
+}
+
+impl Debug for Message {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::Simple(cont) => write!(f, "Simple({:?})", cont),
+            Self::WithReply(cont, _) => write!(f, "WithReply({:?}, ...)", cont),
+            Self::Disconnect => write!(f, "Disconnect")
+        }
+    }
 }
 
 /* ---------- */
 
 pub trait IntoMessage {
     fn into_msg(self) -> Message;
-    fn into_msg_with_reply(self) -> Message;
+    fn into_msg_with_reply(self) -> (Message, ReplyReceiver);
 }
 
 impl<T: Any + Send + 'static> IntoMessage for T {
@@ -73,7 +293,8 @@ impl<T: Any + Send + 'static> IntoMessage for T {
         Message::Simple(Content::from(self))
     }
 
-    fn into_msg_with_reply(self) -> Message {
-        Message::WithReply(Content::from(self))
+    fn into_msg_with_reply(self) -> (Message, ReplyReceiver) {
+        let (reply_send, reply_recv) = channel::reply_channel();
+        (Message::WithReply(Content::from(self), reply_send), reply_recv)
     }
 }
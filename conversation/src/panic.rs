@@ -0,0 +1,19 @@
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+
+/* ---------- */
+
+// Runs a unit hook, a panic is turned into its message instead of unwinding the loop thread.
+pub(crate) fn catch_unwind<R>(f: impl FnOnce() -> R) -> Result<R, String> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(panic_message)
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(msg) => *msg,
+        Err(payload) => match payload.downcast_ref::<&'static str>() {
+            Some(msg) => String::from(*msg),
+            None => String::from("unknown panic")
+        }
+    }
+}
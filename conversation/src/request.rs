@@ -0,0 +1,103 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+
+use crate::envelope::Envelope;
+use crate::message::{Content, IntoContent};
+use crate::unit::{Unit, StopReason};
+
+/* ---------- */
+
+pub trait Request: Any + Send + 'static {
+    type Response: Any + Send + 'static;
+}
+
+/* ---------- */
+
+type Handler<T> = Box<dyn FnMut(&mut T, Content) -> Result<Content, Content> + Send + 'static>;
+
+pub(crate) struct Requests<T> {
+    handlers: HashMap<TypeId, Handler<T>>
+}
+
+impl<T> Requests<T> {
+    pub(crate) fn new() -> Self {
+        Self {
+            handlers: HashMap::new()
+        }
+    }
+
+    pub(crate) fn add<Q, F>(&mut self, mut handler: F)
+    where
+        Q: Request,
+        F: FnMut(&mut T, Q) -> Q::Response + Send + 'static
+    {
+        let handler = move |obj: &mut T, data: Content| {
+            data.downcast::<Q>().map(|req| handler(obj, req).into_content())
+        };
+
+        self.handlers.insert(TypeId::of::<Q>(), Box::new(handler));
+    }
+
+    fn handle(&mut self, obj: &mut T, data: Content) -> Result<Content, Content> {
+        if self.handlers.is_empty() {
+            return Err(data)
+        }
+
+        match self.handlers.get_mut(&data.inner().type_id()) {
+            Some(handler) => handler(obj, data),
+            None => Err(data)
+        }
+    }
+}
+
+/* ---------- */
+
+// Answers the registered request types itself and hands everything else to the unit.
+pub(crate) struct Responder<T> {
+    obj: T,
+    requests: Requests<T>
+}
+
+impl<T> Responder<T> {
+    pub(crate) fn new(obj: T, requests: Requests<T>) -> Self {
+        Self {
+            obj,
+            requests
+        }
+    }
+}
+
+impl<T: Unit> Unit for Responder<T> {
+    fn on_message(&mut self, data: Content) {
+        if let Err(data) = self.requests.handle(&mut self.obj, data) {
+            self.obj.on_message(data)
+        }
+    }
+
+    fn on_message_with_reply(&mut self, data: Content) -> Content {
+        match self.requests.handle(&mut self.obj, data) {
+            Ok(reply) => reply,
+            Err(data) => self.obj.on_message_with_reply(data)
+        }
+    }
+
+    fn on_envelope(&mut self, envelope: &Envelope) {
+        self.obj.on_envelope(envelope)
+    }
+
+    fn on_start(&mut self) {
+        self.obj.on_start()
+    }
+
+    fn on_stop(&mut self, reason: StopReason) {
+        self.obj.on_stop(reason)
+    }
+
+    fn on_idle(&mut self) {
+        self.obj.on_idle()
+    }
+
+    fn should_stop(&self) -> bool {
+        self.obj.should_stop()
+    }
+}
@@ -0,0 +1,79 @@
+use std::time::{Duration, Instant};
+
+use crate::channel::{MessageReceiver, ReplyReceiver, PendingReply};
+use crate::{TrySelectError, SelectTimeoutError};
+
+/* ---------- */
+
+#[derive(Default)]
+pub struct Select<'a> {
+    inner: crossbeam_channel::Select<'a>,
+    ops: Vec<(usize, usize)>,
+    next_index: usize
+}
+
+impl<'a> Select<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn recv(&mut self, recv: &'a MessageReceiver) -> usize {
+        let ops = recv.wait_lanes().iter().map(|lane| self.inner.recv(lane)).collect();
+        self.add(ops)
+    }
+
+    pub fn reply(&mut self, reply: &'a ReplyReceiver) -> usize {
+        let op = self.inner.recv(reply.reply_recver());
+        self.add(vec![op])
+    }
+
+    pub fn pending<R>(&mut self, pending: &'a PendingReply<R>) -> usize {
+        self.reply(pending.reply_recver())
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        self.ops.retain(|(op, idx)| {
+            if *idx == index {
+                self.inner.remove(*op);
+                return false
+            }
+
+            true
+        });
+    }
+
+    pub fn ready(&mut self) -> usize {
+        let op = self.inner.ready();
+        self.index_of(op)
+    }
+
+    pub fn try_ready(&mut self) -> Result<usize, TrySelectError> {
+        let op = self.inner.try_ready()?;
+        Ok(self.index_of(op))
+    }
+
+    pub fn ready_timeout(&mut self, timeout: Duration) -> Result<usize, SelectTimeoutError> {
+        let op = self.inner.ready_timeout(timeout)?;
+        Ok(self.index_of(op))
+    }
+
+    pub fn ready_deadline(&mut self, deadline: Instant) -> Result<usize, SelectTimeoutError> {
+        let op = self.inner.ready_deadline(deadline)?;
+        Ok(self.index_of(op))
+    }
+
+    fn add(&mut self, ops: Vec<usize>) -> usize {
+        let index = self.next_index;
+        self.next_index += 1;
+
+        self.ops.extend(ops.into_iter().map(|op| (op, index)));
+        index
+    }
+
+    fn index_of(&self, op: usize) -> usize {
+        self.ops.iter()
+            .find(|(registered, _)| *registered == op)
+            .map(|(_, index)| *index)
+            .expect("operation registered by this Select")
+    }
+}
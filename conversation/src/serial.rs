@@ -0,0 +1,173 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt::{Formatter, Result as FmtResult};
+use std::sync::{OnceLock, RwLock};
+
+use serde::de::{self, DeserializeOwned, DeserializeSeed, SeqAccess, Visitor};
+use serde::ser::{self, SerializeTuple};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::RegisterTypeError;
+use crate::message::{Content, Message};
+
+/* ---------- */
+
+type SerializeFn = for<'a> fn(&'a (dyn Any + Send + 'static)) -> &'a dyn erased_serde::Serialize;
+type DeserializeFn = fn(&mut dyn erased_serde::Deserializer) -> Result<Content, erased_serde::Error>;
+
+#[derive(Clone, Copy)]
+struct Entry {
+    tag: &'static str,
+    serialize: SerializeFn,
+    deserialize: DeserializeFn
+}
+
+#[derive(Default)]
+struct Registry {
+    by_type: HashMap<TypeId, Entry>,
+    by_tag: HashMap<&'static str, TypeId>
+}
+
+fn registry() -> &'static RwLock<Registry> {
+    static REGISTRY: OnceLock<RwLock<Registry>> = OnceLock::new();
+    REGISTRY.get_or_init(Default::default)
+}
+
+fn serialize_as<'a, T: Serialize + 'static>(obj: &'a (dyn Any + Send + 'static)) -> &'a dyn erased_serde::Serialize {
+    obj.downcast_ref::<T>().expect("registry entry does not match content type")
+}
+
+fn deserialize_as<T: DeserializeOwned + Send + 'static>(de: &mut dyn erased_serde::Deserializer) -> Result<Content, erased_serde::Error> {
+    erased_serde::deserialize::<T>(de).map(Content::from)
+}
+
+pub fn register_type<T>(tag: &'static str) -> Result<(), RegisterTypeError>
+where
+    T: Serialize + DeserializeOwned + Send + 'static
+{
+    let mut reg = registry().write().unwrap();
+    let type_id = TypeId::of::<T>();
+
+    match reg.by_tag.get(tag) {
+        Some(id) if *id == type_id => return Ok(()),
+        Some(_) => return Err(RegisterTypeError(tag)),
+        None => {}
+    }
+
+    if let Some(old) = reg.by_type.get(&type_id).map(|entry| entry.tag) {
+        reg.by_tag.remove(old);
+    }
+
+    reg.by_tag.insert(tag, type_id);
+    reg.by_type.insert(type_id, Entry {
+        tag,
+        serialize: serialize_as::<T>,
+        deserialize: deserialize_as::<T>
+    });
+
+    Ok(())
+}
+
+pub fn is_registered<T: 'static>() -> bool {
+    registry().read().unwrap().by_type.contains_key(&TypeId::of::<T>())
+}
+
+/* ---------- */
+
+impl Content {
+    pub fn tag(&self) -> Option<&'static str> {
+        registry().read().unwrap().by_type.get(&self.inner().type_id()).map(|entry| entry.tag)
+    }
+}
+
+impl Serialize for Content {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let entry = registry().read().unwrap().by_type.get(&self.inner().type_id()).copied();
+
+        let entry = entry.ok_or_else(|| ser::Error::custom(
+            format_args!("type {} is not registered for serialization", self.type_name())
+        ))?;
+
+        let mut tuple = serializer.serialize_tuple(2)?;
+        tuple.serialize_element(entry.tag)?;
+        tuple.serialize_element((entry.serialize)(self.inner()))?;
+        tuple.end()
+    }
+}
+
+struct ContentSeed(DeserializeFn);
+
+impl<'de> DeserializeSeed<'de> for ContentSeed {
+    type Value = Content;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Content, D::Error> {
+        let mut erased = <dyn erased_serde::Deserializer>::erase(deserializer);
+        (self.0)(&mut erased).map_err(de::Error::custom)
+    }
+}
+
+struct ContentVisitor;
+
+impl<'de> Visitor<'de> for ContentVisitor {
+    type Value = Content;
+
+    fn expecting(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "a tagged Content")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Content, A::Error> {
+        let tag: String = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+
+        let deserialize = {
+            let reg = registry().read().unwrap();
+            reg.by_tag.get(tag.as_str()).and_then(|id| reg.by_type.get(id)).map(|entry| entry.deserialize)
+        };
+
+        let deserialize = deserialize.ok_or_else(|| de::Error::custom(
+            format_args!("tag {} is not registered for deserialization", tag)
+        ))?;
+
+        seq.next_element_seed(ContentSeed(deserialize))?.ok_or_else(|| de::Error::invalid_length(1, &self))
+    }
+}
+
+impl<'de> Deserialize<'de> for Content {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Content, D::Error> {
+        deserializer.deserialize_tuple(2, ContentVisitor)
+    }
+}
+
+/* ---------- */
+
+#[derive(Serialize)]
+#[serde(rename = "Message")]
+enum MessageRef<'a> {
+    Simple(&'a Content),
+    Disconnect
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "Message")]
+enum MessageOwned {
+    Simple(Content),
+    Disconnect
+}
+
+impl Serialize for Message {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Simple(cont) => MessageRef::Simple(cont).serialize(serializer),
+            Self::WithReply(..) => Err(ser::Error::custom("a message waiting for a reply can not be serialized")),
+            Self::Disconnect => MessageRef::Disconnect.serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Message {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Message, D::Error> {
+        match MessageOwned::deserialize(deserializer)? {
+            MessageOwned::Simple(cont) => Ok(Self::Simple(cont)),
+            MessageOwned::Disconnect => Ok(Self::Disconnect)
+        }
+    }
+}
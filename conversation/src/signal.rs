@@ -0,0 +1,123 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::task::{Context, Poll, Waker};
+
+use crate::{RecvError, TryRecvError};
+
+/* ---------- */
+
+#[derive(Default)]
+pub(crate) struct Signal {
+    armed: AtomicBool,
+    wakers: Mutex<Vec<Waker>>
+}
+
+impl Signal {
+    pub(crate) fn register(&self, waker: &Waker) {
+        if let Ok(mut wakers) = self.wakers.lock() {
+            if !wakers.iter().any(|registered| registered.will_wake(waker)) {
+                wakers.push(waker.clone());
+            }
+
+            self.armed.store(true, Ordering::SeqCst);
+        }
+    }
+
+    pub(crate) fn notify(&self) {
+        if !self.armed.swap(false, Ordering::SeqCst) {
+            return
+        }
+
+        let wakers = match self.wakers.lock() {
+            Ok(mut wakers) => std::mem::take(&mut *wakers),
+            _ => return
+        };
+
+        wakers.into_iter().for_each(Waker::wake);
+    }
+}
+
+/* ---------- */
+
+// Must be declared after the crossbeam sender it guards, so that the
+// waiters it wakes on drop can observe the disconnection.
+pub(crate) struct Notifier(Arc<Signal>);
+
+impl Notifier {
+    pub(crate) fn new(signal: &Arc<Signal>) -> Self {
+        Self(Arc::clone(signal))
+    }
+
+    pub(crate) fn notify(&self) {
+        self.0.notify()
+    }
+
+    pub(crate) fn signal(&self) -> &Arc<Signal> {
+        &self.0
+    }
+}
+
+impl Clone for Notifier {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl Drop for Notifier {
+    fn drop(&mut self) {
+        self.0.notify()
+    }
+}
+
+/* ---------- */
+
+pub(crate) fn poll_recv<T, F>(try_recv: F, signal: &Signal, cx: &mut Context<'_>) -> Poll<Result<T, RecvError>>
+where
+    F: Fn() -> Result<T, TryRecvError>
+{
+    let try_recv = || match try_recv() {
+        Ok(val) => Some(Ok(val)),
+        Err(TryRecvError::Disconnected) => Some(Err(RecvError)),
+        Err(TryRecvError::Empty) => None
+    };
+
+    if let Some(res) = try_recv() {
+        return Poll::Ready(res)
+    }
+
+    signal.register(cx.waker());
+
+    match try_recv() {
+        Some(res) => Poll::Ready(res),
+        None => Poll::Pending
+    }
+}
+
+/* ---------- */
+
+pub(crate) struct Recv<'a, F> {
+    try_recv: F,
+    signal: &'a Signal
+}
+
+impl<'a, F> Recv<'a, F> {
+    pub(crate) fn new(try_recv: F, signal: &'a Signal) -> Self {
+        Self {
+            try_recv,
+            signal
+        }
+    }
+}
+
+impl<T, F> Future for Recv<'_, F>
+where
+    F: Fn() -> Result<T, TryRecvError> + Unpin
+{
+    type Output = Result<T, RecvError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        poll_recv(&self.try_recv, self.signal, cx)
+    }
+}
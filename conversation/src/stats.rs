@@ -0,0 +1,84 @@
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+
+/* ---------- */
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ChannelStats {
+    pub sent: u64,
+    pub replies_sent: u64,
+    pub expired: u64,
+    pub high_water_mark: usize
+}
+
+/* ---------- */
+
+#[derive(Default)]
+pub(crate) struct ChannelState {
+    senders: AtomicUsize,
+    receivers: AtomicUsize,
+    enabled: AtomicBool,
+    sent: AtomicU64,
+    replies_sent: AtomicU64,
+    expired: AtomicU64,
+    high_water_mark: AtomicUsize
+}
+
+impl ChannelState {
+    pub(crate) fn add_sender(&self) {
+        self.senders.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn remove_sender(&self) {
+        self.senders.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_receiver(&self) {
+        self.receivers.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn remove_receiver(&self) {
+        self.receivers.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn sender_count(&self) -> usize {
+        self.senders.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn receiver_count(&self) -> usize {
+        self.receivers.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn enable(&self) {
+        self.enabled.store(true, Ordering::Relaxed)
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn record_sent(&self, queue_len: usize) {
+        self.sent.fetch_add(1, Ordering::Relaxed);
+        self.high_water_mark.fetch_max(queue_len, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_reply(&self) {
+        self.replies_sent.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_expired(&self) {
+        self.expired.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn snapshot(&self) -> Option<ChannelStats> {
+        if !self.is_enabled() {
+            return None
+        }
+
+        Some(ChannelStats {
+            sent: self.sent.load(Ordering::Relaxed),
+            replies_sent: self.replies_sent.load(Ordering::Relaxed),
+            expired: self.expired.load(Ordering::Relaxed),
+            high_water_mark: self.high_water_mark.load(Ordering::Relaxed)
+        })
+    }
+}
@@ -0,0 +1,470 @@
+use std::collections::{HashMap, VecDeque};
+use std::ops::Range;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crossbeam_channel::{Sender, Receiver, RecvTimeoutError};
+
+use crate::{Cluster, ReplyError, SupervisorError};
+use crate::channel::{self, MessageReceiver, MessageSender, Priority};
+use crate::message::Content;
+use crate::panic;
+use crate::unit::{self, Unit, StopReason};
+
+/* ---------- */
+
+const REAP_INTERVAL: Duration = Duration::from_millis(10);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    OneForOne,
+    OneForAll,
+    RestForOne
+}
+
+/* ---------- */
+
+struct Exit {
+    id: usize,
+    reason: Result<StopReason, String>,
+    mailbox: Option<MessageReceiver>
+}
+
+enum Event {
+    Add(Child),
+    Exit(Exit),
+    Intensity(usize, Duration),
+    Start(Monitor),
+    Stop(Priority),
+    Shutdown(Option<String>)
+}
+
+// Sent through a child mailbox to stop a single instance, a token left by an older instance is ignored.
+struct StopToken(u64);
+
+pub(crate) struct Monitor {
+    id: usize,
+    generation: u64,
+    events: Sender<Event>
+}
+
+impl Monitor {
+    pub(crate) fn intercept(&self, data: &Content) -> Option<bool> {
+        data.as_ref::<StopToken>().map(|token| token.0 == self.generation)
+    }
+
+    pub(crate) fn exit(self, reason: Result<StopReason, String>, mailbox: Option<MessageReceiver>) {
+        let _ = self.events.send(Event::Exit(Exit {
+            id: self.id,
+            reason,
+            mailbox
+        }));
+    }
+}
+
+/* ---------- */
+
+type Spawn = Box<dyn FnMut(MessageReceiver, Monitor) + Send + 'static>;
+
+enum Kind {
+    Unit {
+        spawn: Spawn,
+        sender: MessageSender,
+        mailbox: Option<MessageReceiver>
+    },
+    Supervisor(Supervisor)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Running,
+    Stopped,
+    Terminated
+}
+
+struct Child {
+    id: usize,
+    kind: Kind,
+    generation: u64,
+    status: Status
+}
+
+impl Child {
+    fn start(&mut self, events: &Sender<Event>) {
+        self.generation += 1;
+
+        let monitor = Monitor {
+            id: self.id,
+            generation: self.generation,
+            events: events.clone()
+        };
+
+        match &mut self.kind {
+            Kind::Unit { spawn, mailbox, .. } => match mailbox.take() {
+                Some(rx) => spawn(rx, monitor),
+                None => return self.status = Status::Terminated
+            },
+            Kind::Supervisor(sup) => {
+                if sup.is_stopped() || sup.events.send(Event::Start(monitor)).is_err() {
+                    return self.status = Status::Terminated
+                }
+            }
+        }
+
+        self.status = Status::Running;
+    }
+
+    fn stop(&self, level: Priority) {
+        match &self.kind {
+            Kind::Unit { sender, .. } => {
+                let _ = sender.send_msg_priority(level, StopToken(self.generation));
+            }
+            Kind::Supervisor(sup) => {
+                let _ = sup.events.send(Event::Stop(level));
+            }
+        }
+    }
+
+    // a child supervisor that shut down on its own never reports its exit
+    fn reap(&mut self) {
+        if let Kind::Supervisor(sup) = &self.kind {
+            if self.status == Status::Running && sup.is_stopped() {
+                self.status = Status::Terminated;
+            }
+        }
+    }
+
+    fn exited(&mut self, rx: Option<MessageReceiver>) {
+        if let Kind::Unit { mailbox, .. } = &mut self.kind {
+            *mailbox = rx.or(mailbox.take());
+        }
+
+        self.status = Status::Stopped;
+    }
+
+    fn terminate(&mut self, failure: Option<&str>) {
+        match &mut self.kind {
+            Kind::Unit { mailbox, .. } => {
+                if let (Some(rx), Some(msg)) = (mailbox.take(), failure) {
+                    rx.fail_pending(ReplyError::UnitPanicked(String::from(msg)));
+                }
+            }
+            Kind::Supervisor(sup) => {
+                let _ = sup.events.send(Event::Shutdown(failure.map(String::from)));
+            }
+        }
+
+        self.status = Status::Terminated;
+    }
+}
+
+/* ---------- */
+
+pub struct Supervisor {
+    thread: Option<JoinHandle<()>>,
+    events: Sender<Event>,
+    children: HashMap<&'static str, Option<MessageSender>>,
+    next_id: usize
+}
+
+impl Supervisor {
+    pub fn new(strategy: Strategy) -> Self {
+        let (send, recv) = crossbeam_channel::unbounded();
+        let supervision = Supervision::new(strategy, send.clone(), recv);
+
+        let thread = thread::spawn(move || supervision.run());
+
+        Self {
+            thread: Some(thread),
+            events: send,
+            children: HashMap::new(),
+            next_id: 0
+        }
+    }
+
+    pub fn with_intensity(self, max_restarts: usize, period: Duration) -> Self {
+        let _ = self.events.send(Event::Intensity(max_restarts, period));
+        self
+    }
+
+    pub fn spawn_pipe<T, F>(&mut self, name: &'static str, mut factory: F) -> Result<MessageSender, SupervisorError>
+    where
+        T: Unit + Send + 'static,
+        F: FnMut() -> T + Send + 'static
+    {
+        self.check_name(name)?;
+
+        let (send, recv) = channel::channel();
+
+        let spawn = move |rx: MessageReceiver, monitor: Monitor| match panic::catch_unwind(&mut factory) {
+            Ok(obj) => {
+                thread::spawn(move || unit::receive_loop_thread(obj, rx, Some(monitor)));
+            }
+            Err(msg) => monitor.exit(Err(msg), Some(rx))
+        };
+
+        let kind = Kind::Unit {
+            spawn: Box::new(spawn),
+            sender: send.clone(),
+            mailbox: Some(recv)
+        };
+
+        self.add(name, Some(send.clone()), kind)?;
+        Ok(send)
+    }
+
+    pub fn spawn_in<T, F>(&mut self, cluster: &mut Cluster, name: &'static str, mut factory: F) -> Result<(), SupervisorError>
+    where
+        T: Unit + Send + 'static,
+        F: FnMut() -> T + Send + 'static
+    {
+        self.check_name(name)?;
+
+        let (send, recv) = channel::channel();
+        let spawner = cluster.add_supervised(name, send.clone()).map_err(SupervisorError::ClusterError)?;
+
+        let spawn = move |rx: MessageReceiver, monitor: Monitor| match panic::catch_unwind(&mut factory) {
+            Ok(obj) => spawner.spawn(obj, rx, monitor),
+            Err(msg) => monitor.exit(Err(msg), Some(rx))
+        };
+
+        let kind = Kind::Unit {
+            spawn: Box::new(spawn),
+            sender: send,
+            mailbox: Some(recv)
+        };
+
+        self.add(name, None, kind).inspect_err(|_| {
+            let _ = cluster.remove(name);
+        })
+    }
+
+    pub fn add_supervisor(&mut self, name: &'static str, child: Supervisor) -> Result<(), SupervisorError> {
+        self.check_name(name)?;
+        self.add(name, None, Kind::Supervisor(child))
+    }
+
+    pub fn sender(&self, name: &str) -> Option<&MessageSender> {
+        self.children.get(name).and_then(Option::as_ref)
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.thread.as_ref().is_none_or(JoinHandle::is_finished)
+    }
+
+    fn check_name(&self, name: &'static str) -> Result<(), SupervisorError> {
+        match self.children.contains_key(name) {
+            true => Err(SupervisorError::NameAlreadyUsed(name)),
+            false => Ok(())
+        }
+    }
+
+    fn add(&mut self, name: &'static str, sender: Option<MessageSender>, kind: Kind) -> Result<(), SupervisorError> {
+        let child = Child {
+            id: self.next_id,
+            kind,
+            generation: 0,
+            status: Status::Stopped
+        };
+
+        if self.events.send(Event::Add(child)).is_err() {
+            return Err(SupervisorError::Stopped)
+        }
+
+        self.next_id += 1;
+        self.children.insert(name, sender);
+        Ok(())
+    }
+}
+
+impl Drop for Supervisor {
+    fn drop(&mut self) {
+        if let Some(thread) = self.thread.take() {
+            let _ = self.events.send(Event::Shutdown(None));
+            let _ = thread.join();
+        }
+    }
+}
+
+/* ---------- */
+
+struct Supervision {
+    strategy: Strategy,
+    max_restarts: usize,
+    period: Duration,
+    restarts: VecDeque<Instant>,
+    children: Vec<Child>,
+    parent: Option<Monitor>,
+    running: bool,
+    events: Sender<Event>,
+    inbox: Receiver<Event>,
+    backlog: VecDeque<Event>
+}
+
+impl Supervision {
+    fn new(strategy: Strategy, events: Sender<Event>, inbox: Receiver<Event>) -> Self {
+        Self {
+            strategy,
+            max_restarts: 3,
+            period: Duration::from_secs(5),
+            restarts: VecDeque::new(),
+            children: Vec::new(),
+            parent: None,
+            running: true,
+            events,
+            inbox,
+            backlog: VecDeque::new()
+        }
+    }
+
+    fn run(mut self) {
+        while let Some(event) = self.next_event() {
+            match event {
+                Event::Add(mut child) => {
+                    if self.running {
+                        child.start(&self.events);
+                    }
+
+                    self.children.push(child)
+                }
+                Event::Exit(exit) => {
+                    if !self.on_exit(exit) {
+                        return
+                    }
+                }
+                Event::Intensity(max_restarts, period) => {
+                    self.max_restarts = max_restarts;
+                    self.period = period;
+                }
+                Event::Start(monitor) => {
+                    self.parent = Some(monitor);
+
+                    if !self.running {
+                        self.running = true;
+                        self.restarts.clear();
+                        self.start(self.all());
+                    }
+                }
+                Event::Stop(level) => {
+                    if self.running {
+                        self.running = false;
+                        self.stop(self.all(), level);
+
+                        if let Some(parent) = self.parent.take() {
+                            parent.exit(Ok(StopReason::Restart), None);
+                        }
+                    }
+                }
+                Event::Shutdown(failure) => return self.shutdown(failure.as_deref())
+            }
+        }
+    }
+
+    fn next_event(&mut self) -> Option<Event> {
+        self.backlog.pop_front().or_else(|| self.inbox.recv().ok())
+    }
+
+    fn on_exit(&mut self, exit: Exit) -> bool {
+        let pos = match self.children.iter().position(|child| child.id == exit.id) {
+            Some(pos) => pos,
+            None => return true
+        };
+
+        self.children[pos].exited(exit.mailbox);
+
+        match exit.reason {
+            Ok(_) => {
+                self.children[pos].terminate(None);
+                true
+            }
+            Err(msg) => self.restart(pos, msg)
+        }
+    }
+
+    fn restart(&mut self, pos: usize, msg: String) -> bool {
+        if !self.allow_restart() {
+            return self.escalate(msg)
+        }
+
+        let range = match self.strategy {
+            Strategy::OneForOne => pos..pos + 1,
+            Strategy::OneForAll => self.all(),
+            Strategy::RestForOne => pos..self.children.len()
+        };
+
+        self.stop(range.clone(), Priority::Urgent);
+        self.start(range);
+        true
+    }
+
+    fn allow_restart(&mut self) -> bool {
+        let now = Instant::now();
+
+        while self.restarts.front().is_some_and(|at| now.duration_since(*at) > self.period) {
+            self.restarts.pop_front();
+        }
+
+        self.restarts.push_back(now);
+        self.restarts.len() <= self.max_restarts
+    }
+
+    // past the restart intensity the whole subtree goes down, a parent decides what happens next
+    fn escalate(&mut self, msg: String) -> bool {
+        self.running = false;
+        self.stop(self.all(), Priority::Urgent);
+
+        match self.parent.take() {
+            Some(parent) => {
+                parent.exit(Err(msg), None);
+                true
+            }
+            None => {
+                self.shutdown(Some(&msg));
+                false
+            }
+        }
+    }
+
+    fn shutdown(&mut self, failure: Option<&str>) {
+        self.stop(self.all(), Priority::Low);
+        self.children.iter_mut().rev().for_each(|child| child.terminate(failure));
+    }
+
+    fn start(&mut self, range: Range<usize>) {
+        for child in &mut self.children[range] {
+            if child.status == Status::Stopped {
+                child.start(&self.events);
+            }
+        }
+    }
+
+    fn stop(&mut self, range: Range<usize>, level: Priority) {
+        self.children[range.clone()].iter()
+            .filter(|child| child.status == Status::Running)
+            .for_each(|child| child.stop(level));
+
+        while self.children[range.clone()].iter().any(|child| child.status == Status::Running) {
+            let exit = match self.inbox.recv_timeout(REAP_INTERVAL) {
+                Ok(Event::Exit(exit)) => exit,
+                Ok(event) => {
+                    self.backlog.push_back(event);
+                    continue
+                }
+                Err(RecvTimeoutError::Timeout) => {
+                    self.children[range.clone()].iter_mut().for_each(Child::reap);
+                    continue
+                }
+                Err(RecvTimeoutError::Disconnected) => return
+            };
+
+            match self.children[range.clone()].iter_mut().find(|child| child.id == exit.id) {
+                Some(child) => child.exited(exit.mailbox),
+                None => self.backlog.push_back(Event::Exit(exit))
+            }
+        }
+    }
+
+    fn all(&self) -> Range<usize> {
+        0..self.children.len()
+    }
+}
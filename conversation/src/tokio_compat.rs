@@ -0,0 +1,39 @@
+use std::any::Any;
+use std::time::Duration;
+
+use crate::message::{Content, Message};
+use crate::channel::{MessageReceiver, ReplyReceiver, PendingReply};
+use crate::{RecvTimeoutError, ReplyError};
+
+/* ---------- */
+
+impl MessageReceiver {
+    pub async fn recv_msg_timeout_async(&self, timeout: Duration) -> Result<Message, RecvTimeoutError> {
+        match tokio::time::timeout(timeout, self.recv_msg_async()).await {
+            Ok(res) => res.map_err(|_| RecvTimeoutError::Disconnected),
+            Err(_) => Err(RecvTimeoutError::Timeout)
+        }
+    }
+}
+
+/* ---------- */
+
+impl ReplyReceiver {
+    pub async fn recv_timeout_async(&self, timeout: Duration) -> Result<Content, ReplyError> {
+        match tokio::time::timeout(timeout, self.recv_async()).await {
+            Ok(res) => res,
+            Err(_) => Err(ReplyError::Timeout)
+        }
+    }
+}
+
+/* ---------- */
+
+impl<R: Any + Send + 'static> PendingReply<R> {
+    pub async fn wait_timeout_async(&self, timeout: Duration) -> Result<R, ReplyError> {
+        match tokio::time::timeout(timeout, self.wait_async()).await {
+            Ok(res) => res,
+            Err(_) => Err(ReplyError::Timeout)
+        }
+    }
+}
@@ -0,0 +1,251 @@
+use std::any::Any;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+use crate::{SendMessageError, TryReplyError};
+use crate::{Cluster, ClusterError};
+use crate::envelope::Envelope;
+use crate::message::{Content, IntoContent};
+use crate::channel::MessageSender;
+use crate::unit::{Unit, StopReason, Builder, Pipe};
+
+/* ---------- */
+
+pub trait TryUnit: Send + 'static {
+    type Error: Debug + Send + 'static;
+
+    fn try_on_message(&mut self, data: Content) -> Result<(), Self::Error>;
+    fn try_on_message_with_reply(&mut self, data: Content) -> Result<Content, Self::Error>;
+
+    fn on_envelope(&mut self, _envelope: &Envelope) {}
+
+    fn on_start(&mut self) {}
+    fn on_stop(&mut self, _reason: StopReason) {}
+    fn on_idle(&mut self) {}
+
+    fn should_stop(&self) -> bool {
+        false
+    }
+
+    fn build_try(self) -> Builder<'static, Fallible<Self>> where Self: Sized {
+        Fallible::new(self).build_unit()
+    }
+}
+
+/* ---------- */
+
+pub enum ErrorPolicy<U> {
+    Log,
+    Stop,
+    Restart(Box<dyn FnMut() -> U + Send + 'static>),
+    Sink(MessageSender)
+}
+
+impl<U> ErrorPolicy<U> {
+    pub fn restart<F: FnMut() -> U + Send + 'static>(factory: F) -> Self {
+        Self::Restart(Box::new(factory))
+    }
+}
+
+/* ---------- */
+
+// the reply of a with-reply message whose handler failed
+struct Failure<E>(E);
+
+pub struct Fallible<U> {
+    unit: U,
+    policy: ErrorPolicy<U>,
+    stopped: bool
+}
+
+impl<U: TryUnit> Fallible<U> {
+    pub fn new(unit: U) -> Self {
+        Self {
+            unit,
+            policy: ErrorPolicy::Log,
+            stopped: false
+        }
+    }
+
+    pub fn with_error_policy(mut self, policy: ErrorPolicy<U>) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    // an error that has a caller to go back to never reaches the sink
+    fn recover(&mut self, err: &U::Error) {
+        match &mut self.policy {
+            ErrorPolicy::Log => println!("unit error : {:?}", err),
+            ErrorPolicy::Stop => self.stopped = true,
+            ErrorPolicy::Restart(factory) => {
+                self.unit.on_stop(StopReason::Restart);
+                self.unit = factory();
+                self.unit.on_start();
+            }
+            ErrorPolicy::Sink(_) => {}
+        }
+    }
+}
+
+impl<U: TryUnit> Unit for Fallible<U> {
+    fn on_message(&mut self, data: Content) {
+        let err = match self.unit.try_on_message(data) {
+            Ok(()) => return,
+            Err(err) => err
+        };
+
+        match &self.policy {
+            ErrorPolicy::Sink(sink) => {
+                if let Err(err) = sink.send_msg(err) {
+                    println!("failed to send error : {}", err);
+                }
+            }
+            _ => self.recover(&err)
+        }
+    }
+
+    fn on_message_with_reply(&mut self, data: Content) -> Content {
+        match self.unit.try_on_message_with_reply(data) {
+            Ok(reply) => reply,
+            Err(err) => {
+                self.recover(&err);
+                Failure(err).into_content()
+            }
+        }
+    }
+
+    fn on_envelope(&mut self, envelope: &Envelope) {
+        self.unit.on_envelope(envelope)
+    }
+
+    fn on_start(&mut self) {
+        self.unit.on_start()
+    }
+
+    fn on_stop(&mut self, reason: StopReason) {
+        self.unit.on_stop(reason)
+    }
+
+    fn on_idle(&mut self) {
+        self.unit.on_idle()
+    }
+
+    fn should_stop(&self) -> bool {
+        self.stopped || self.unit.should_stop()
+    }
+}
+
+impl<'a, U: TryUnit> Builder<'a, Fallible<U>> {
+    pub fn with_error_policy(mut self, policy: ErrorPolicy<U>) -> Self {
+        self.unit_mut().policy = policy;
+        self
+    }
+
+    pub fn spawn_try_pipe(self) -> TryPipe<U::Error> {
+        TryPipe {
+            pipe: self.spawn_pipe(),
+            _error: PhantomData
+        }
+    }
+
+    // the sink is looked up in the cluster the unit is registered with
+    pub fn with_error_sink(mut self, name: &'static str) -> Result<Self, ClusterError<'a>> {
+        let cluster = self.cluster().ok_or(ClusterError::RegistrationError)?;
+        let sink = cluster.sender(name).ok_or(ClusterError::IdNotFound(name))?.clone();
+
+        self.unit_mut().policy = ErrorPolicy::Sink(sink);
+        Ok(self)
+    }
+
+    pub fn spawn_try(self) -> Result<TrySender<U::Error>, ClusterError<'a>> {
+        Ok(TrySender::from(self.spawn_in_cluster()?))
+    }
+}
+
+impl Cluster {
+    pub fn register_try<U: TryUnit>(&mut self, obj: U) -> Builder<'_, Fallible<U>> {
+        self.register(Fallible::new(obj))
+    }
+}
+
+/* ---------- */
+
+fn convert<R: 'static, E: 'static>(reply: Content) -> Result<R, TryReplyError<E>> {
+    match reply.downcast::<Failure<E>>() {
+        Ok(Failure(err)) => Err(TryReplyError::Failed(err)),
+        Err(reply) => Ok(reply.convert::<R>()?)
+    }
+}
+
+/* ---------- */
+
+pub struct TrySender<E> {
+    inner: MessageSender,
+    _error: PhantomData<fn() -> E>
+}
+
+impl<E: Send + 'static> TrySender<E> {
+    pub fn send<M: Any + Send + 'static>(&self, msg: M) -> Result<(), SendMessageError> {
+        self.inner.send_msg(msg)
+    }
+
+    pub fn send_with_reply<M, R>(&self, msg: M) -> Result<R, TryReplyError<E>>
+    where
+        M: Any + Send + 'static,
+        R: Any + Send + 'static
+    {
+        convert(self.inner.send_msg_with_reply(msg)?.recv()?)
+    }
+
+    pub fn as_erased(&self) -> &MessageSender {
+        &self.inner
+    }
+
+    pub fn into_erased(self) -> MessageSender {
+        self.inner
+    }
+}
+
+impl<E> TrySender<E> {
+    fn from(inner: MessageSender) -> Self {
+        Self {
+            inner,
+            _error: PhantomData
+        }
+    }
+}
+
+impl<E> Clone for TrySender<E> {
+    fn clone(&self) -> Self {
+        Self::from(self.inner.clone())
+    }
+}
+
+/* ---------- */
+
+pub struct TryPipe<E> {
+    pipe: Pipe,
+    _error: PhantomData<fn() -> E>
+}
+
+impl<E: Send + 'static> TryPipe<E> {
+    pub fn send<M: Any + Send + 'static>(&self, msg: M) -> Result<(), SendMessageError> {
+        self.pipe.send(msg)
+    }
+
+    pub fn send_with_reply<M, R>(&self, msg: M) -> Result<R, TryReplyError<E>>
+    where
+        M: Any + Send + 'static,
+        R: Any + Send + 'static
+    {
+        convert(self.pipe.sender().send_msg_with_reply(msg)?.recv()?)
+    }
+
+    pub fn sender(&self) -> TrySender<E> {
+        TrySender::from(self.pipe.sender().clone())
+    }
+
+    pub fn as_erased(&self) -> &Pipe {
+        &self.pipe
+    }
+}
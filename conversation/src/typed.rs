@@ -0,0 +1,238 @@
+use std::marker::PhantomData;
+use std::time::{Duration, Instant};
+
+use crate::{SendMessageError, SendMessageWithReplyError, SendReplyError, RecvError, TryRecvError, RecvTimeoutError};
+use crate::{Cluster, ClusterError};
+use crate::message::{Content, Message, IntoContent};
+use crate::channel::{self, MessageReceiver, MessageSender, ReplySender, PendingReply};
+use crate::unit::{Unit, Builder, Pipe};
+
+/* ---------- */
+
+pub trait TypedUnit: Send + 'static {
+    type Message: Send + 'static;
+    type Reply: Send + 'static;
+
+    fn handle(&mut self, msg: Self::Message) -> Self::Reply;
+
+    fn build_typed(self) -> Builder<'static, Typed<Self>> where Self: Sized {
+        Typed(self).build_unit()
+    }
+}
+
+/* ---------- */
+
+pub struct Typed<U>(pub U);
+
+impl<U: TypedUnit> Unit for Typed<U> {
+    fn on_message(&mut self, data: Content) {
+        if let Some(msg) = data.into::<U::Message>() {
+            self.0.handle(msg);
+        }
+    }
+
+    fn on_message_with_reply(&mut self, data: Content) -> Content {
+        match data.into::<U::Message>() {
+            Some(msg) => self.0.handle(msg).into_content(),
+            None => ().into_content()
+        }
+    }
+}
+
+impl<'a, U: TypedUnit> Builder<'a, Typed<U>> {
+    pub fn spawn_typed_pipe(self) -> TypedPipe<U::Message, U::Reply> {
+        TypedPipe {
+            pipe: self.spawn_pipe(),
+            _types: PhantomData
+        }
+    }
+
+    pub fn spawn_typed(self) -> Result<TypedSender<U::Message, U::Reply>, ClusterError<'a>> {
+        Ok(TypedSender::from(self.spawn_in_cluster()?))
+    }
+}
+
+impl Cluster {
+    pub fn register_typed<U: TypedUnit>(&mut self, obj: U) -> Builder<'_, Typed<U>> {
+        self.register(Typed(obj))
+    }
+}
+
+/* ---------- */
+
+pub struct TypedSender<M, R = ()> {
+    inner: MessageSender,
+    _types: PhantomData<fn(M) -> R>
+}
+
+impl<M: Send + 'static, R: Send + 'static> TypedSender<M, R> {
+    pub fn send(&self, msg: M) -> Result<(), SendMessageError> {
+        self.inner.send_msg(msg)
+    }
+
+    pub fn send_with_reply(&self, msg: M) -> Result<R, SendMessageWithReplyError> {
+        Ok(self.request(msg)?.wait()?)
+    }
+
+    pub fn request(&self, msg: M) -> Result<PendingReply<R>, SendMessageError> {
+        self.inner.request(msg)
+    }
+
+    pub fn as_erased(&self) -> &MessageSender {
+        &self.inner
+    }
+
+    pub fn into_erased(self) -> MessageSender {
+        self.inner
+    }
+}
+
+impl<M, R> TypedSender<M, R> {
+    fn from(inner: MessageSender) -> Self {
+        Self {
+            inner,
+            _types: PhantomData
+        }
+    }
+}
+
+impl<M, R> Clone for TypedSender<M, R> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            _types: PhantomData
+        }
+    }
+}
+
+/* ---------- */
+
+pub enum TypedMessage<M, R> {
+    Simple(M),
+    WithReply(M, TypedReplySender<R>),
+    Disconnect
+}
+
+/* ---------- */
+
+pub struct TypedReplySender<R> {
+    inner: ReplySender,
+    _type: PhantomData<fn(R)>
+}
+
+impl<R: Send + 'static> TypedReplySender<R> {
+    pub fn send(self, reply: R) -> Result<(), SendReplyError> {
+        self.inner.send(reply.into_content())
+    }
+}
+
+/* ---------- */
+
+pub struct TypedReceiver<M, R = ()> {
+    inner: MessageReceiver,
+    _types: PhantomData<fn(M) -> R>
+}
+
+impl<M: Send + 'static, R: Send + 'static> TypedReceiver<M, R> {
+    pub fn recv(&self) -> Result<TypedMessage<M, R>, RecvError> {
+        loop {
+            if let Some(msg) = Self::convert(self.inner.recv_msg()?) {
+                return Ok(msg)
+            }
+        }
+    }
+
+    pub fn try_recv(&self) -> Result<TypedMessage<M, R>, TryRecvError> {
+        loop {
+            if let Some(msg) = Self::convert(self.inner.try_recv_msg()?) {
+                return Ok(msg)
+            }
+        }
+    }
+
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<TypedMessage<M, R>, RecvTimeoutError> {
+        self.recv_deadline(Instant::now() + timeout)
+    }
+
+    pub fn recv_deadline(&self, deadline: Instant) -> Result<TypedMessage<M, R>, RecvTimeoutError> {
+        loop {
+            if let Some(msg) = Self::convert(self.inner.recv_msg_deadline(deadline)?) {
+                return Ok(msg)
+            }
+        }
+    }
+
+    pub fn as_erased(&self) -> &MessageReceiver {
+        &self.inner
+    }
+
+    pub fn into_erased(self) -> MessageReceiver {
+        self.inner
+    }
+
+    fn convert(msg: Message) -> Option<TypedMessage<M, R>> {
+        match msg {
+            Message::Simple(data) => data.into::<M>().map(TypedMessage::Simple),
+            Message::WithReply(data, reply_to) => {
+                let reply_to = TypedReplySender {
+                    inner: reply_to,
+                    _type: PhantomData
+                };
+
+                data.into::<M>().map(|msg| TypedMessage::WithReply(msg, reply_to))
+            }
+            Message::Disconnect => Some(TypedMessage::Disconnect)
+        }
+    }
+}
+
+impl<M, R> Clone for TypedReceiver<M, R> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            _types: PhantomData
+        }
+    }
+}
+
+/* ---------- */
+
+pub struct TypedPipe<M, R = ()> {
+    pipe: Pipe,
+    _types: PhantomData<fn(M) -> R>
+}
+
+impl<M: Send + 'static, R: Send + 'static> TypedPipe<M, R> {
+    pub fn send(&self, msg: M) -> Result<(), SendMessageError> {
+        self.pipe.send(msg)
+    }
+
+    pub fn send_with_reply(&self, msg: M) -> Result<R, SendMessageWithReplyError> {
+        self.pipe.send_with_reply(msg)
+    }
+
+    pub fn request(&self, msg: M) -> Result<PendingReply<R>, SendMessageError> {
+        self.pipe.request(msg)
+    }
+
+    pub fn sender(&self) -> TypedSender<M, R> {
+        TypedSender::from(self.pipe.sender().clone())
+    }
+
+    pub fn as_erased(&self) -> &Pipe {
+        &self.pipe
+    }
+}
+
+/* ---------- */
+
+pub fn typed_channel<M, R>() -> (TypedSender<M, R>, TypedReceiver<M, R>) {
+    let (send, recv) = channel::channel();
+
+    let recver = TypedReceiver {
+        inner: recv,
+        _types: PhantomData
+    };
+
+    (TypedSender::from(send), recver)
+}
@@ -1,9 +1,16 @@
 use std::any::Any;
 use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
 
 use crate::{SendMessageWithReplyError, SendMessageError, Cluster, ClusterError};
+use crate::{TrySendMessageError, SendMessageTimeoutError, ReplyError};
+use crate::envelope::Envelope;
+use crate::panic;
+use crate::supervisor::Monitor;
+use crate::request::{Request, Requests, Responder};
 use crate::message::{Content, Message};
-use crate::channel::{self, MessageReceiver, MessageSender};
+use crate::channel::{self, MessageReceiver, MessageSender, PendingReply, Priority};
+use crate::stats::ChannelStats;
 
 /* ---------- */
 
@@ -11,11 +18,31 @@ pub trait Unit: Send + 'static {
     fn on_message(&mut self, data: Content);
     fn on_message_with_reply(&mut self, data: Content) -> Content;
 
+    fn on_envelope(&mut self, _envelope: &Envelope) {}
+
+    fn on_start(&mut self) {}
+    fn on_stop(&mut self, _reason: StopReason) {}
+    fn on_idle(&mut self) {}
+
+    fn should_stop(&self) -> bool {
+        false
+    }
+
     fn build_unit(self) -> Builder<'static, Self> where Self: Sized {
         Builder::<Self>::new(self)
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopReason {
+    Disconnected,
+    Removed,
+    ClusterDropped,
+    Panicked,
+    Restart,
+    Stopped
+}
+
 /* ---------- */
 
 pub trait WithContext {
@@ -38,25 +65,117 @@ impl Pipe {
         self.msg_send.send_msg(data)
     }
 
+    pub fn send_priority<M: Any + Send + 'static>(&self, level: Priority, data: M) -> Result<(), SendMessageError> {
+        self.msg_send.send_msg_priority(level, data)
+    }
+
+    pub fn try_send<M: Any + Send + 'static>(&self, data: M) -> Result<(), TrySendMessageError> {
+        self.msg_send.try_send_msg(data)
+    }
+
+    pub fn send_timeout<M>(&self, data: M, timeout: Duration) -> Result<(), SendMessageTimeoutError>
+    where
+        M: Any + Send + 'static
+    {
+        self.msg_send.send_msg_timeout(data, timeout)
+    }
+
     pub fn send_with_reply<M, R>(&self, data: M) -> Result<R, SendMessageWithReplyError>
     where
         M: Any + Send + 'static,
         R: Any + Send + 'static
     {
-        self.msg_send.send_msg_with_reply(data)?;
-        let reply = self.msg_send.recv_reply()?;
+        let reply = self.msg_send.send_msg_with_reply(data)?.recv()?;
 
-        match reply.into::<R>() {
-            Some(val) => Ok(val),
-            None => Err(SendMessageWithReplyError::ConvertContentError)
-        }
+        Ok(reply.convert::<R>()?)
+    }
+
+    pub async fn send_with_reply_async<M, R>(&self, data: M) -> Result<R, SendMessageWithReplyError>
+    where
+        M: Any + Send + 'static,
+        R: Any + Send + 'static
+    {
+        let reply = self.msg_send.send_msg_with_reply(data)?.recv_async().await?;
+
+        Ok(reply.convert::<R>()?)
+    }
+
+    pub fn request<M, R>(&self, data: M) -> Result<PendingReply<R>, SendMessageError>
+    where
+        M: Any + Send + 'static,
+        R: Any + Send + 'static
+    {
+        self.msg_send.request(data)
+    }
+
+    pub fn ask<Q: Request>(&self, req: Q) -> Result<Q::Response, SendMessageWithReplyError> {
+        self.send_with_reply::<Q, Q::Response>(req)
+    }
+
+    pub async fn ask_async<Q: Request>(&self, req: Q) -> Result<Q::Response, SendMessageWithReplyError> {
+        self.send_with_reply_async::<Q, Q::Response>(req).await
+    }
+
+    pub fn send_deadline<M: Any + Send + 'static>(&self, data: M, deadline: Instant) -> Result<(), SendMessageError> {
+        self.msg_send.send_msg_deadline(data, deadline)
+    }
+
+    pub fn request_deadline<M, R>(&self, data: M, deadline: Instant) -> Result<PendingReply<R>, SendMessageError>
+    where
+        M: Any + Send + 'static,
+        R: Any + Send + 'static
+    {
+        self.msg_send.request_deadline(data, deadline)
+    }
+
+    pub fn send_with_envelope<M>(&self, envelope: Envelope, data: M) -> Result<(), SendMessageError>
+    where
+        M: Any + Send + 'static
+    {
+        self.msg_send.send_msg_with_envelope(envelope, data)
+    }
+
+    pub fn request_with_envelope<M, R>(&self, envelope: Envelope, data: M) -> Result<PendingReply<R>, SendMessageError>
+    where
+        M: Any + Send + 'static,
+        R: Any + Send + 'static
+    {
+        self.msg_send.request_with_envelope(envelope, data)
+    }
+
+    pub fn len(&self) -> usize {
+        self.msg_send.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.msg_send.is_empty()
+    }
+
+    pub fn capacity(&self) -> Option<usize> {
+        self.msg_send.capacity()
+    }
+
+    pub fn sender_count(&self) -> usize {
+        self.msg_send.sender_count()
+    }
+
+    pub fn receiver_count(&self) -> usize {
+        self.msg_send.receiver_count()
+    }
+
+    pub fn stats(&self) -> Option<ChannelStats> {
+        self.msg_send.stats()
+    }
+
+    pub(crate) fn sender(&self) -> &MessageSender {
+        &self.msg_send
     }
 }
 
 impl Drop for Pipe {
     fn drop(&mut self) {
         if let Some(thread) = self.thread.take() {
-            let _ = self.msg_send.disconnect();
+            self.msg_send.disconnect();
             let _ = thread.join();
         }
     }
@@ -66,39 +185,59 @@ impl Drop for Pipe {
 
 pub struct Builder<'a, T> {
     obj: T,
-    sender: MessageSender,
-    recver: MessageReceiver,
+    capacity: Option<usize>,
+    stats: bool,
+    context: Option<fn(&mut T, MessageSender)>,
+    requests: Requests<T>,
     cluster: Option<&'a mut Cluster>,
     id: Option<&'static str>
 }
 
 impl<'a, T: Unit + Send + 'static> Builder<'a, T> {
     pub(crate) fn new(obj: T) -> Self {
-        let (send, recv) = channel::channel();
-
         Self {
             obj,
-            sender: send,
-            recver: recv,
+            capacity: None,
+            stats: false,
+            context: None,
+            requests: Requests::new(),
             cluster: None,
             id: None
         }
     }
 
-    pub fn spawn_pipe(self) -> Pipe {
-        let thread = thread::spawn(move || receive_loop_thread(self.obj, self.recver));
+    pub fn spawn_pipe(mut self) -> Pipe {
+        let (send, recv) = self.open_channel();
+        let obj = Responder::new(self.obj, self.requests);
+        let thread = thread::spawn(move || receive_loop_thread(obj, recv, None));
 
         Pipe {
             thread: Some(thread),
-            msg_send: self.sender
+            msg_send: send
         }
     }
 
     pub fn spawn(self) -> Result<(), ClusterError<'a>> {
-        let cluster = self.cluster.ok_or(ClusterError::RegistrationError)?;
+        self.spawn_in_cluster().map(|_| ())
+    }
+
+    pub(crate) fn spawn_in_cluster(mut self) -> Result<MessageSender, ClusterError<'a>> {
+        let cluster = self.cluster.take().ok_or(ClusterError::RegistrationError)?;
         let id = self.id.ok_or(ClusterError::UnsetIdError)?;
+        let (send, recv) = self.open_channel();
 
-        cluster.add_unique(id, self.obj, self.sender, self.recver)
+        cluster.add_unique(id, Responder::new(self.obj, self.requests), send.clone(), recv)?;
+        Ok(send)
+    }
+
+    pub fn with_capacity(mut self, cap: usize) -> Self {
+        self.capacity = Some(cap);
+        self
+    }
+
+    pub fn with_stats(mut self) -> Self {
+        self.stats = true;
+        self
     }
 
     pub fn with_name(mut self, id: &'static str) -> Self {
@@ -110,34 +249,117 @@ impl<'a, T: Unit + Send + 'static> Builder<'a, T> {
         self.cluster = Some(cluster_ref);
         self
     }
+
+    pub fn on_request<Q, F>(mut self, handler: F) -> Self
+    where
+        Q: Request,
+        F: FnMut(&mut T, Q) -> Q::Response + Send + 'static
+    {
+        self.requests.add(handler);
+        self
+    }
+}
+
+impl<T> Builder<'_, T> {
+    pub(crate) fn unit_mut(&mut self) -> &mut T {
+        &mut self.obj
+    }
+
+    pub(crate) fn cluster(&self) -> Option<&Cluster> {
+        self.cluster.as_deref()
+    }
+
+    fn open_channel(&mut self) -> (MessageSender, MessageReceiver) {
+        let (send, recv) = match self.capacity {
+            Some(cap) => channel::channel_bounded(cap),
+            None => channel::channel()
+        };
+
+        if self.stats {
+            send.enable_stats();
+        }
+
+        if let Some(context) = self.context {
+            context(&mut self.obj, send.clone());
+        }
+
+        (send, recv)
+    }
 }
 
 impl<T: WithContext> Builder<'_, T> {
     pub fn with_context(mut self) -> Self {
-        self.obj.with_context(self.sender.clone());
+        self.context = Some(T::with_context);
         self
     }
 }
 
 /* ---------- */
 
-fn receive_loop_thread<T: Unit>(mut obj: T, recv: MessageReceiver) {
+pub(crate) fn receive_loop_thread<T: Unit>(mut obj: T, recv: MessageReceiver, monitor: Option<Monitor>) {
+    let reason = panic::catch_unwind(|| obj.on_start()).and_then(|_| receive_loop(&mut obj, &recv, monitor.as_ref()));
+
+    stop_unit(&mut obj, recv, reason, monitor)
+}
+
+fn receive_loop<T: Unit>(obj: &mut T, recv: &MessageReceiver, monitor: Option<&Monitor>) -> Result<StopReason, String> {
+    loop {
+        let (envelope, msg) = match recv.recv_envelope() {
+            Ok(letter) => letter,
+            Err(_) => {
+                println!("failed to rev msg");
+                return Ok(StopReason::Disconnected)
+            }
+        };
 
-    while let Ok(msg) = recv.recv_msg() {
         match msg {
-            Message::Disconnect => return,
-            Message::Simple(content) => {
-                obj.on_message(content)
+            Message::Disconnect => return Ok(StopReason::Disconnected),
+            Message::Simple(content) => match monitor.and_then(|monitor| monitor.intercept(&content)) {
+                Some(true) => return Ok(StopReason::Restart),
+                Some(false) => continue,
+                None => panic::catch_unwind(|| {
+                    obj.on_envelope(&envelope);
+                    obj.on_message(content)
+                })?
             }
-            Message::WithReply(content) => {
-                let reply = obj.on_message_with_reply(content);
-                if let Err(err) = recv.send_reply(reply) {
-                    println!("failed to send reply : {}", err);
-                    return;
+            Message::WithReply(content, reply_to) => {
+                let reply = panic::catch_unwind(|| {
+                    obj.on_envelope(&envelope);
+                    obj.on_message_with_reply(content)
+                });
+
+                match reply {
+                    Ok(reply) => if let Err(err) = recv.send_reply(reply_to, reply) {
+                        println!("failed to send reply : {}", err);
+                    }
+                    Err(msg) => {
+                        reply_to.fail(ReplyError::UnitPanicked(msg.clone()));
+                        return Err(msg)
+                    }
                 }
             }
         }
+
+        if panic::catch_unwind(|| obj.should_stop())? {
+            return Ok(StopReason::Stopped)
+        }
+
+        if recv.is_empty() {
+            panic::catch_unwind(|| obj.on_idle())?;
+        }
     }
+}
+
+// a supervised unit hands its mailbox back so the next instance keeps the same address
+pub(crate) fn stop_unit<U>(obj: &mut U, recv: MessageReceiver, reason: Result<StopReason, String>, monitor: Option<Monitor>)
+where
+    U: Unit + ?Sized
+{
+    let _ = panic::catch_unwind(|| obj.on_stop(reason.clone().unwrap_or(StopReason::Panicked)));
 
-    println!("failed to rev msg")
+    match (monitor, reason) {
+        (Some(monitor), reason) => monitor.exit(reason, Some(recv)),
+        (None, Err(msg)) => recv.fail_pending(ReplyError::UnitPanicked(msg)),
+        (None, Ok(_)) => {}
+    }
 }
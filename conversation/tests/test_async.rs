@@ -0,0 +1,159 @@
+use std::future::Future;
+use std::pin::pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake};
+use std::thread::{self, Thread};
+use std::time::Duration;
+
+use conversation::{Content, IntoContent, Message, Unit};
+
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark()
+    }
+}
+
+fn block_on<F: Future>(fut: F) -> F::Output {
+    let mut fut = pin!(fut);
+    let waker = Arc::new(ThreadWaker(thread::current())).into();
+    let mut cx = Context::from_waker(&waker);
+
+    loop {
+        match fut.as_mut().poll(&mut cx) {
+            Poll::Ready(val) => return val,
+            Poll::Pending => thread::park()
+        }
+    }
+}
+
+struct Echo;
+
+impl Unit for Echo {
+    fn on_message(&mut self, _: Content) {}
+    fn on_message_with_reply(&mut self, data: Content) -> Content {
+        data
+    }
+}
+
+#[test]
+fn recv_msg_async() {
+    let (tx, rx) = conversation::channel();
+
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(10));
+        tx.send_msg(1).unwrap();
+        tx.send_msg(2).unwrap();
+    });
+
+    let msg = block_on(rx.recv_msg_async()).unwrap();
+    assert_eq!(msg.into(), Some(1));
+
+    let msg = rx.recv_msg().unwrap();
+    assert_eq!(msg.into(), Some(2));
+
+    handle.join().unwrap();
+
+    assert!(block_on(rx.recv_msg_async()).is_err());
+}
+
+#[test]
+fn recv_msg_async_rendezvous() {
+    let (tx, rx) = conversation::channel_bounded(0);
+    let (done, received) = crossbeam_channel::bounded(1);
+
+    thread::spawn(move || {
+        let msgs = (0..3).map(|_| block_on(rx.recv_msg_async()).unwrap().into::<i32>()).collect::<Vec<_>>();
+        let _ = done.send(msgs);
+    });
+
+    thread::spawn(move || {
+        thread::sleep(Duration::from_millis(10));
+
+        for i in 0..3 {
+            let _ = tx.send_msg(i);
+        }
+    });
+
+    assert_eq!(received.recv_timeout(Duration::from_secs(3)).unwrap(), vec![Some(0), Some(1), Some(2)]);
+}
+
+#[test]
+fn recv_reply_async() {
+    let (tx, rx) = conversation::channel();
+
+    let handle = thread::spawn(move || {
+        if let Message::WithReply(data, reply_to) = rx.recv_msg().unwrap() {
+            thread::sleep(Duration::from_millis(10));
+            reply_to.send(data).unwrap();
+        }
+
+        if let Message::WithReply(_, reply_to) = rx.recv_msg().unwrap() {
+            drop(reply_to)
+        }
+    });
+
+    let reply = tx.send_msg_with_reply("Hello").unwrap();
+    assert_eq!(block_on(reply.recv_async()).unwrap().into(), Some("Hello"));
+
+    let reply = tx.request::<_, i32>(1).unwrap();
+    assert!(block_on(reply.wait_async()).is_err());
+
+    handle.join().unwrap();
+}
+
+#[test]
+fn send_with_reply_async() {
+    let msger = Echo.build_unit().spawn_pipe();
+
+    let reply: i32 = block_on(msger.send_with_reply_async(1)).unwrap();
+    assert_eq!(reply, 1);
+
+    let reply: Result<String, _> = block_on(msger.send_with_reply_async(1));
+    assert!(reply.is_err());
+}
+
+#[tokio::test]
+async fn mixed_blocking_and_async() {
+    let (tx, rx) = conversation::channel();
+
+    let handle = thread::spawn(move || {
+        while let Ok(msg) = rx.recv_msg() {
+            if let Message::WithReply(data, reply_to) = msg {
+                let val = data.into::<i32>().unwrap();
+                reply_to.send((val + 1).into_content()).unwrap();
+            }
+        }
+    });
+
+    for i in 0..10 {
+        let reply = tx.request::<_, i32>(i).unwrap();
+        assert_eq!(reply.wait_async().await.unwrap(), i + 1);
+    }
+
+    drop(tx);
+    handle.join().unwrap();
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn tokio_timeouts() {
+    use conversation::{RecvTimeoutError, ReplyError};
+
+    let (tx, rx) = conversation::channel();
+
+    let res = rx.recv_msg_timeout_async(Duration::from_millis(10)).await;
+    assert_eq!(res.err(), Some(RecvTimeoutError::Timeout));
+
+    let reply = tx.request::<_, i32>(1).unwrap();
+    let res = reply.wait_timeout_async(Duration::from_millis(10)).await;
+    assert_eq!(res.err(), Some(ReplyError::Timeout));
+
+    match rx.recv_msg_timeout_async(Duration::from_millis(10)).await.unwrap() {
+        Message::WithReply(data, reply_to) => reply_to.send(data).unwrap(),
+        _ => panic!("expected a message with reply")
+    }
+
+    assert_eq!(reply.wait_timeout_async(Duration::from_millis(10)).await.unwrap(), 1);
+}
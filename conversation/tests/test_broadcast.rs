@@ -0,0 +1,103 @@
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use conversation::Message;
+
+#[test]
+fn publish() {
+    let publisher = conversation::broadcast();
+
+    let sub1 = publisher.subscribe();
+    let sub2 = publisher.subscribe();
+
+    assert_eq!(publisher.publish(1), 2);
+    assert_eq!(publisher.publish(String::from("Hello")), 2);
+
+    for sub in [sub1, sub2] {
+        assert_eq!(sub.recv_msg().unwrap().into(), Some(1));
+        assert_eq!(sub.recv_msg().unwrap().into(), Some(String::from("Hello")));
+    }
+}
+
+#[test]
+fn publish_shared() {
+    let publisher = conversation::broadcast();
+
+    let sub1 = publisher.subscribe();
+    let sub2 = publisher.subscribe();
+
+    assert_eq!(publisher.publish_shared(vec![0u8; 1024]), 2);
+
+    let data1 = sub1.recv_msg().unwrap().into::<Arc<Vec<u8>>>().unwrap();
+    let data2 = sub2.recv_msg().unwrap().into::<Arc<Vec<u8>>>().unwrap();
+
+    assert!(Arc::ptr_eq(&data1, &data2));
+}
+
+#[test]
+fn dropped_subscribers() {
+    let publisher = conversation::broadcast();
+
+    let sub1 = publisher.subscribe();
+    let sub2 = publisher.subscribe();
+    assert_eq!(publisher.subscriber_count(), 2);
+
+    drop(sub1);
+    assert_eq!(publisher.subscriber_count(), 1);
+
+    let sub3 = publisher.subscribe();
+    drop(sub2);
+    assert_eq!(publisher.publish(1), 1);
+
+    assert_eq!(sub3.recv_msg().unwrap().into(), Some(1));
+}
+
+#[test]
+fn publisher_dropped() {
+    let publisher = conversation::broadcast();
+    let other = publisher.clone();
+
+    let subs = (0..4).map(|_| publisher.subscribe()).collect::<Vec<_>>();
+
+    let handles = subs.into_iter().map(|sub| {
+        thread::spawn(move || sub.iter().filter(|msg| matches!(msg, Message::Simple(_))).count())
+    }).collect::<Vec<_>>();
+
+    for i in 0..10 {
+        other.publish(i);
+    }
+
+    drop(publisher);
+    drop(other);
+
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), 10);
+    }
+}
+
+#[test]
+fn full_subscriber() {
+    let publisher = conversation::broadcast();
+    let slow = publisher.subscribe_bounded(1);
+
+    assert_eq!(publisher.publish(1), 1);
+
+    let blocked = publisher.clone();
+    let handle = thread::spawn(move || blocked.publish(2));
+
+    thread::sleep(Duration::from_millis(20));
+
+    let fast = publisher.subscribe();
+    assert_eq!(publisher.subscriber_count(), 2);
+    assert!(fast.try_recv_msg().is_err());
+
+    assert_eq!(slow.recv_msg().unwrap().into(), Some(1));
+    assert_eq!(handle.join().unwrap(), 1);
+    assert_eq!(slow.recv_msg().unwrap().into(), Some(2));
+
+    drop(slow);
+    assert_eq!(publisher.publish(3), 1);
+    assert_eq!(fast.recv_msg().unwrap().into(), Some(3));
+    assert_eq!(publisher.subscriber_count(), 1);
+}
@@ -1,6 +1,7 @@
 use std::thread;
+use std::time::{Duration, Instant};
 
-use conversation::IntoContent;
+use conversation::{ChannelStats, Envelope, IntoContent, Message, Priority, ReplyError, TryRecvError, RecvTimeoutError};
 
 #[test]
 fn send_message() {
@@ -25,23 +26,59 @@ fn send_message_with_reply() {
     let (tx, rx) = conversation::channel();
 
     let handle = thread::spawn(move || {
-        let data = rx.recv_msg().unwrap();
-        assert_eq!(data.into(), Some(1));
-        rx.send_reply(2.into_content()).unwrap();
+        for _ in 0..2 {
+            match rx.recv_msg().unwrap() {
+                Message::WithReply(data, reply_to) => {
+                    if data.is::<i32>() {
+                        assert_eq!(data.into(), Some(1));
+                        rx.send_reply(reply_to, 2.into_content()).unwrap();
+                    } else {
+                        assert_eq!(data.into(), Some("Hello"));
+                        reply_to.send("It's a reply".into_content()).unwrap();
+                    }
+                }
+                _ => panic!("expected a message with reply")
+            }
+        }
+    });
 
-        let data = rx.recv_msg().unwrap();
-        assert_eq!(data.into(), Some("Hello"));
-        rx.send_reply("It's a reply".into_content()).unwrap();
+    let reply = tx.send_msg_with_reply(1).unwrap();
+    assert_eq!(reply.recv().unwrap().into(), Some(2));
+
+    let reply = tx.send_msg_with_reply("Hello").unwrap();
+    assert_eq!(reply.recv().unwrap().into(), Some("It's a reply"));
+
+    handle.join().unwrap();
+}
+
+#[test]
+fn replies_are_not_shared_between_clones() {
+    let (tx, rx) = conversation::channel();
+
+    let handle = thread::spawn(move || {
+        while let Ok(msg) = rx.recv_msg() {
+            if let Message::WithReply(data, reply_to) = msg {
+                let val = data.into::<usize>().unwrap();
+                reply_to.send((val * 2).into_content()).unwrap();
+            }
+        }
     });
 
-    tx.send_msg(1).unwrap();
-    let resp = tx.recv_reply().unwrap();
-    assert_eq!(resp.into(), Some(2));
+    let callers = (0..8usize).map(|idx| {
+        let tx = tx.clone();
 
-    tx.send_msg("Hello").unwrap();
-    let resp = tx.recv_reply().unwrap();
-    assert_eq!(resp.into(), Some("It's a reply"));
+        thread::spawn(move || {
+            for i in 0..100 {
+                let val = idx * 1000 + i;
+                let reply = tx.send_msg_with_reply(val).unwrap();
+                assert_eq!(reply.recv().unwrap().into(), Some(val * 2));
+            }
+        })
+    }).collect::<Vec<_>>();
+
+    callers.into_iter().for_each(|caller| caller.join().unwrap());
 
+    drop(tx);
     handle.join().unwrap();
 }
 
@@ -76,3 +113,309 @@ fn error_on_recv() {
     let ret = rx.recv_msg();
     assert!(ret.is_err())
 }
+
+#[test]
+fn bounded_channel() {
+    let (tx, rx) = conversation::channel_bounded(1);
+
+    tx.try_send_msg(1).unwrap();
+
+    let err = tx.try_send_msg(2).unwrap_err();
+    assert!(err.is_full());
+    assert_eq!(err.into(), Some(2));
+
+    let err = tx.send_msg_timeout(3, Duration::from_millis(10)).unwrap_err();
+    assert!(err.is_timeout());
+    assert_eq!(err.into(), Some(3));
+
+    assert_eq!(rx.recv_msg().unwrap().into(), Some(1));
+
+    tx.send_msg_timeout(4, Duration::from_millis(10)).unwrap();
+    assert_eq!(rx.recv_msg().unwrap().into(), Some(4));
+
+    drop(rx);
+
+    let err = tx.try_send_msg(5).unwrap_err();
+    assert!(err.is_disconnected());
+    assert_eq!(err.into(), Some(5));
+}
+
+#[test]
+fn bounded_channel_backpressure() {
+    let (tx, rx) = conversation::channel_bounded(2);
+
+    let handle = thread::spawn(move || {
+        for i in 0..100 {
+            tx.send_msg(i).unwrap();
+        }
+    });
+
+    for i in 0..100 {
+        assert_eq!(rx.recv_msg().unwrap().into(), Some(i));
+    }
+
+    handle.join().unwrap();
+}
+
+#[test]
+fn bounded_channel_priorities() {
+    let (tx, rx) = conversation::channel_bounded(2);
+
+    tx.send_msg_priority(Priority::Low, 1).unwrap();
+    tx.send_msg_priority(Priority::Urgent, 2).unwrap();
+
+    assert_eq!(tx.capacity(), Some(2));
+    assert_eq!(rx.capacity(), Some(2));
+    assert_eq!(tx.len(), 2);
+
+    assert!(tx.try_send_msg(3).unwrap_err().is_full());
+    assert!(tx.send_msg_timeout(3, Duration::from_millis(10)).unwrap_err().is_timeout());
+
+    assert_eq!(rx.recv_msg().unwrap().into(), Some(2));
+    tx.try_send_msg(3).unwrap();
+    assert!(tx.try_send_msg(4).unwrap_err().is_full());
+
+    let handle = thread::spawn(move || tx.send_msg_priority(Priority::High, 4).unwrap());
+
+    assert_eq!(rx.recv_msg().unwrap().into(), Some(3));
+    handle.join().unwrap();
+
+    assert_eq!(rx.recv_msg().unwrap().into(), Some(4));
+    assert_eq!(rx.recv_msg().unwrap().into(), Some(1));
+}
+
+#[test]
+fn rendezvous_channel() {
+    let (tx, rx) = conversation::channel_bounded(0);
+
+    assert_eq!(tx.capacity(), Some(0));
+    assert!(tx.try_send_msg(1).unwrap_err().is_full());
+    assert!(tx.send_msg_timeout(1, Duration::from_millis(10)).unwrap_err().is_timeout());
+
+    assert_eq!(rx.try_recv_msg().err(), Some(TryRecvError::Empty));
+
+    tx.try_send_msg(1).unwrap();
+    assert!(tx.try_send_msg(2).unwrap_err().is_full());
+    assert_eq!(rx.recv_msg().unwrap().into(), Some(1));
+
+    let handle = thread::spawn(move || {
+        for i in 0..10 {
+            tx.send_msg(i).unwrap();
+        }
+    });
+
+    for i in 0..10 {
+        assert_eq!(rx.recv_msg().unwrap().into(), Some(i));
+    }
+
+    handle.join().unwrap();
+}
+
+#[test]
+fn try_and_timed_recv_msg() {
+    let (tx, rx) = conversation::channel();
+
+    assert_eq!(rx.try_recv_msg().err(), Some(TryRecvError::Empty));
+    assert_eq!(rx.recv_msg_timeout(Duration::from_millis(10)).err(), Some(RecvTimeoutError::Timeout));
+
+    let deadline = Instant::now() + Duration::from_millis(10);
+    assert_eq!(rx.recv_msg_deadline(deadline).err(), Some(RecvTimeoutError::Timeout));
+
+    tx.send_msg(1).unwrap();
+    tx.send_msg(2).unwrap();
+    tx.send_msg(3).unwrap();
+
+    assert_eq!(rx.try_recv_msg().unwrap().into(), Some(1));
+    assert_eq!(rx.recv_msg_timeout(Duration::from_millis(10)).unwrap().into(), Some(2));
+    assert_eq!(rx.recv_msg_deadline(Instant::now()).unwrap().into(), Some(3));
+
+    drop(tx);
+
+    assert_eq!(rx.try_recv_msg().err(), Some(TryRecvError::Disconnected));
+    assert_eq!(rx.recv_msg_timeout(Duration::from_millis(10)).err(), Some(RecvTimeoutError::Disconnected));
+}
+
+#[test]
+fn try_and_timed_recv_reply() {
+    let (tx, rx) = conversation::channel();
+
+    let reply = tx.send_msg_with_reply(()).unwrap();
+
+    assert_eq!(reply.try_recv().err(), Some(ReplyError::NotReady));
+    assert_eq!(reply.recv_timeout(Duration::from_millis(10)).err(), Some(ReplyError::Timeout));
+
+    let handle = thread::spawn(move || {
+        if let Message::WithReply(_, reply_to) = rx.recv_msg().unwrap() {
+            reply_to.send(1.into_content()).unwrap();
+        }
+
+        match rx.recv_msg().unwrap() {
+            Message::WithReply(_, reply_to) => drop(reply_to),
+            _ => panic!("expected a message with reply")
+        }
+    });
+
+    let deadline = Instant::now() + Duration::from_secs(5);
+    assert_eq!(reply.recv_deadline(deadline).unwrap().into(), Some(1));
+
+    let reply = tx.send_msg_with_reply(()).unwrap();
+    handle.join().unwrap();
+
+    assert_eq!(reply.try_recv().err(), Some(ReplyError::Disconnected));
+}
+
+#[test]
+fn request() {
+    let (tx, rx) = conversation::channel();
+
+    let first = tx.request::<_, i32>(1).unwrap();
+    let second = tx.request::<_, i32>(2).unwrap();
+
+    let handle = thread::spawn(move || {
+        for _ in 0..2 {
+            if let Message::WithReply(data, reply_to) = rx.recv_msg().unwrap() {
+                reply_to.send(data).unwrap();
+            }
+        }
+    });
+
+    assert_eq!(second.wait().unwrap(), 2);
+    assert_eq!(first.wait().unwrap(), 1);
+
+    handle.join().unwrap();
+}
+
+#[test]
+fn priority_lanes() {
+    let (tx, rx) = conversation::channel();
+
+    tx.send_msg_priority(Priority::Low, 1).unwrap();
+    tx.send_msg(2).unwrap();
+    tx.send_msg_priority(Priority::High, 3).unwrap();
+    tx.send_msg(4).unwrap();
+    tx.send_msg_priority(Priority::High, 5).unwrap();
+    tx.send_disconnect(Priority::Urgent).unwrap();
+    tx.send_msg_priority(Priority::Urgent, 6).unwrap();
+
+    assert!(matches!(rx.recv_msg().unwrap(), Message::Disconnect));
+
+    let received = rx.try_iter().filter_map(|msg| msg.into::<i32>()).collect::<Vec<_>>();
+    assert_eq!(received, vec![6, 3, 5, 2, 4, 1]);
+
+    tx.send_msg_priority(Priority::Low, 7).unwrap();
+    drop(tx);
+
+    assert_eq!(rx.recv_msg_timeout(Duration::from_millis(10)).unwrap().into(), Some(7));
+    assert!(rx.recv_msg().is_err());
+}
+
+#[test]
+fn queue_depth() {
+    let (tx, rx) = conversation::channel();
+
+    assert!(tx.is_empty() && rx.is_empty());
+    assert_eq!(tx.capacity(), None);
+
+    tx.send_msg(1).unwrap();
+    tx.send_msg_priority(Priority::High, 2).unwrap();
+    assert_eq!(tx.len(), 2);
+    assert_eq!(rx.len(), 2);
+
+    rx.recv_msg().unwrap();
+    assert_eq!(rx.len(), 1);
+
+    let (tx, rx) = conversation::channel_bounded(4);
+    assert_eq!(tx.capacity(), Some(4));
+    assert_eq!(rx.capacity(), Some(4));
+}
+
+#[test]
+fn endpoint_counts() {
+    let (tx, rx) = conversation::channel();
+    assert_eq!((tx.sender_count(), tx.receiver_count()), (1, 1));
+
+    let tx2 = tx.clone();
+    let rx2 = rx.clone();
+    let rx3 = rx.clone();
+    assert_eq!((rx.sender_count(), rx.receiver_count()), (2, 3));
+
+    drop(tx2);
+    drop(rx2);
+    drop(rx3);
+    assert_eq!((tx.sender_count(), tx.receiver_count()), (1, 1));
+}
+
+#[test]
+fn channel_stats() {
+    let (tx, rx) = conversation::channel();
+    assert_eq!(tx.stats(), None);
+
+    tx.enable_stats();
+
+    tx.send_msg(1).unwrap();
+    tx.send_msg(2).unwrap();
+    tx.try_send_msg(3).unwrap();
+    let reply = tx.send_msg_with_reply(4).unwrap();
+
+    for msg in rx.try_iter() {
+        if let Message::WithReply(data, reply_to) = msg {
+            rx.send_reply(reply_to, data).unwrap();
+        }
+    }
+
+    assert_eq!(reply.recv().unwrap().into(), Some(4));
+
+    let expected = ChannelStats {
+        sent: 4,
+        replies_sent: 1,
+        expired: 0,
+        high_water_mark: 4
+    };
+
+    assert_eq!(tx.stats(), Some(expected));
+    assert_eq!(rx.stats(), Some(expected));
+}
+
+#[test]
+fn envelope() {
+    let (tx, rx) = conversation::channel();
+
+    tx.send_msg(1).unwrap();
+    tx.send_msg_with_envelope(Envelope::new().with_sender("me"), 2).unwrap();
+
+    let (first, msg) = rx.try_recv_envelope().unwrap();
+    assert_eq!(msg.into(), Some(1));
+    assert_eq!(first.sender(), None);
+    assert_eq!(first.correlation_id(), None);
+
+    let (second, msg) = rx.recv_envelope().unwrap();
+    assert_eq!(msg.into(), Some(2));
+    assert_eq!(second.sender(), Some("me"));
+    assert_ne!(first.id(), second.id());
+
+    assert_eq!(rx.try_recv_envelope().err(), Some(TryRecvError::Empty));
+}
+
+#[test]
+fn expired_messages() {
+    let (tx, rx) = conversation::channel();
+    tx.enable_stats();
+
+    let past = Instant::now();
+    let future = Instant::now() + Duration::from_secs(60);
+
+    tx.send_msg_deadline(1, past).unwrap();
+    tx.send_msg_with_envelope(Envelope::new().with_ttl(Duration::from_secs(60)), 2).unwrap();
+    let expired = tx.request_deadline::<_, i32>(3, past).unwrap();
+    tx.send_msg_deadline(4, future).unwrap();
+
+    let (envelope, msg) = rx.try_recv_envelope().unwrap();
+    assert_eq!(msg.into(), Some(2));
+    assert!(!envelope.is_expired());
+
+    assert_eq!(rx.try_recv_msg().unwrap().into(), Some(4));
+    assert_eq!(rx.try_recv_msg().err(), Some(TryRecvError::Empty));
+
+    assert_eq!(expired.wait().err(), Some(ReplyError::Expired));
+    assert_eq!(tx.stats().unwrap().expired, 2);
+}
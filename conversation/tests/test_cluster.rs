@@ -1,4 +1,7 @@
-use conversation::{Cluster, ClusterError, Content, IntoContent, Unit};
+use std::sync::{Arc, Barrier, Mutex};
+use std::time::{Duration, Instant};
+
+use conversation::{Cluster, ClusterError, Content, Envelope, IntoContent, MessageSender, Priority, ReplyError, Request, StopReason, Unit};
 
 struct Dummy;
 
@@ -41,6 +44,15 @@ fn manage_cluster() {
     group.remove("1").unwrap();
     group.remove("2").unwrap();
 
+    group.register(DummyI32).with_name("bounded").with_capacity(1).spawn().unwrap();
+
+    for _ in 0..10 {
+        group.send_to("bounded", ()).unwrap();
+    }
+
+    let rep: i32 = group.send_to_with_reply("bounded", ()).unwrap();
+    assert_eq!(rep, 1);
+
 }
 
 #[test]
@@ -70,7 +82,316 @@ fn send_to_with_reply() {
     assert_eq!(rep, "String");
 
     let rep: Result<i32, ClusterError> = group.send_to_with_reply("String", ());
-    assert!(matches!(rep, Err(ClusterError::ContentConversionError)));
+    match rep {
+        Err(ClusterError::ContentConversionError(err)) => {
+            assert_eq!(err.expected, "i32");
+            assert_eq!(err.found, std::any::type_name::<String>());
+        }
+        _ => panic!("expected a conversion error")
+    }
 
     assert!(group.send_to("foo", ()).is_err());
 }
+
+#[test]
+fn send_to_with_reply_from_many_threads() {
+    let mut group = Cluster::new();
+
+    group.register(DummyI32).with_name("i32").spawn().unwrap();
+    group.register(DummyString).with_name("String").spawn().unwrap();
+
+    std::thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                for _ in 0..100 {
+                    let rep: i32 = group.send_to_with_reply("i32", ()).unwrap();
+                    assert_eq!(rep, 1);
+                }
+            });
+
+            scope.spawn(|| {
+                for _ in 0..100 {
+                    let rep: String = group.send_to_with_reply("String", ()).unwrap();
+                    assert_eq!(rep, "String");
+                }
+            });
+        }
+    });
+}
+
+#[test]
+fn request() {
+    let mut group = Cluster::new();
+
+    group.register(DummyI32).with_name("i32").spawn().unwrap();
+    group.register(DummyString).with_name("String").spawn().unwrap();
+
+    let int = group.request::<_, i32>("i32", ()).unwrap();
+    let string = group.request::<_, String>("String", ()).unwrap();
+
+    assert_eq!(string.wait().unwrap(), "String");
+    assert_eq!(int.wait().unwrap(), 1);
+
+    assert!(group.request::<_, i32>("foo", ()).is_err());
+}
+
+#[test]
+fn send_to_priority() {
+    struct Recorder(Arc<Barrier>, Arc<Mutex<Vec<i32>>>);
+
+    impl Unit for Recorder {
+        fn on_message(&mut self, data: Content) {
+            if data.is::<()>() {
+                self.0.wait();
+            } else if let Some(val) = data.into::<i32>() {
+                self.1.lock().unwrap().push(val);
+            }
+        }
+
+        fn on_message_with_reply(&mut self, _: Content) -> Content {
+            ().into_content()
+        }
+    }
+
+    let barrier = Arc::new(Barrier::new(2));
+    let received = Arc::new(Mutex::new(Vec::new()));
+
+    let mut group = Cluster::new();
+    group.register(Recorder(Arc::clone(&barrier), Arc::clone(&received))).with_name("rec").spawn().unwrap();
+
+    group.send_to("rec", ()).unwrap();
+    group.send_to_priority("rec", Priority::Low, 1).unwrap();
+    group.send_to("rec", 2).unwrap();
+    group.send_to_priority("rec", Priority::Urgent, 3).unwrap();
+    group.send_to_priority("rec", Priority::High, 4).unwrap();
+    barrier.wait();
+
+    while received.lock().unwrap().len() < 4 {
+        std::thread::yield_now();
+    }
+
+    assert_eq!(*received.lock().unwrap(), vec![3, 4, 2, 1]);
+}
+
+#[test]
+fn trace_with_envelope() {
+    struct Forward {
+        next: MessageSender,
+        current: Envelope
+    }
+
+    impl Unit for Forward {
+        fn on_message(&mut self, data: Content) {
+            let envelope = self.current.follow_up().with_sender("forward");
+            self.next.send_msg_with_envelope(envelope, data.into::<i32>().unwrap()).unwrap();
+        }
+
+        fn on_message_with_reply(&mut self, _: Content) -> Content {
+            self.current.correlation_id().into_content()
+        }
+
+        fn on_envelope(&mut self, envelope: &Envelope) {
+            self.current = envelope.clone()
+        }
+    }
+
+    let (tx, rx) = conversation::channel();
+    let mut group = Cluster::new();
+
+    group.register(Forward { next: tx, current: Envelope::new() }).with_name("forward").spawn().unwrap();
+
+    let envelope = Envelope::new().with_header("trace", "abc");
+    let id = envelope.id();
+    group.send_to_with_envelope("forward", envelope, 5).unwrap();
+
+    let (envelope, msg) = rx.recv_envelope().unwrap();
+    assert_eq!(msg.into::<i32>(), Some(5));
+    assert_eq!(envelope.sender(), Some("forward"));
+    assert_eq!(envelope.correlation_id(), Some(id));
+    assert_eq!(envelope.header("trace"), Some("abc"));
+    assert_ne!(envelope.id(), id);
+
+    let envelope = Envelope::new().with_correlation_id(42);
+    let reply = group.request_with_envelope::<_, Option<u64>>("forward", envelope, ()).unwrap();
+    assert_eq!(reply.wait().unwrap(), Some(42));
+
+    assert!(matches!(group.send_to_with_envelope("unknown", Envelope::new(), ()), Err(ClusterError::IdNotFound("unknown"))));
+}
+
+#[test]
+fn expired() {
+    let mut group = Cluster::new();
+    group.register(DummyI32).with_name("i32").spawn().unwrap();
+
+    let reply = group.request_deadline::<_, i32>("i32", (), Instant::now()).unwrap();
+    assert_eq!(reply.wait().err(), Some(ReplyError::Expired));
+
+    group.send_to_deadline("i32", (), Instant::now()).unwrap();
+
+    let reply = group.request_deadline::<_, i32>("i32", (), Instant::now() + Duration::from_secs(60)).unwrap();
+    assert_eq!(reply.wait().unwrap(), 1);
+}
+
+#[test]
+fn send_to_many() {
+    struct Frame(MessageSender);
+
+    impl Unit for Frame {
+        fn on_message(&mut self, data: Content) {
+            if let Some(frame) = data.as_ref::<Vec<u8>>() {
+                self.0.send_msg(frame.as_ptr() as usize).unwrap();
+            }
+        }
+
+        fn on_message_with_reply(&mut self, _: Content) -> Content { ().into_content() }
+    }
+
+    let (tx, rx) = conversation::channel();
+    let mut group = Cluster::new();
+
+    for id in ["a", "b", "c"] {
+        group.register(Frame(tx.clone())).with_name(id).spawn().unwrap();
+    }
+
+    group.send_to_many(&["a", "b", "c"], vec![0u8; 1024]).unwrap();
+
+    let seen = (0..3).map(|_| rx.recv_msg().unwrap().into::<usize>().unwrap()).collect::<Vec<_>>();
+    assert!(seen.iter().all(|ptr| *ptr == seen[0]));
+
+    assert!(matches!(group.send_to_many(&["a", "unknown"], 1), Err(ClusterError::IdNotFound("unknown"))));
+    group.send_to_many(&[], 1).unwrap();
+}
+
+#[test]
+fn send_to_many_into() {
+    struct Frame(MessageSender);
+
+    impl Unit for Frame {
+        fn on_message(&mut self, data: Content) {
+            self.0.send_msg(data.into::<Vec<u8>>()).unwrap();
+        }
+
+        fn on_message_with_reply(&mut self, data: Content) -> Content {
+            data.into::<Vec<u8>>().map(|frame| frame.len()).into_content()
+        }
+    }
+
+    let (tx, rx) = conversation::channel();
+    let mut group = Cluster::new();
+
+    for id in ["a", "b", "c"] {
+        group.register(Frame(tx.clone())).with_name(id).spawn().unwrap();
+    }
+
+    group.send_to_many(&["a", "b", "c"], vec![7u8; 1024]).unwrap();
+
+    for _ in 0..3 {
+        assert_eq!(rx.recv_msg().unwrap().into::<Option<Vec<u8>>>(), Some(Some(vec![7u8; 1024])));
+    }
+}
+
+#[test]
+fn ask() {
+    struct Len(String);
+
+    impl Request for Len {
+        type Response = usize;
+    }
+
+    let mut group = Cluster::new();
+    group.register(DummyString).with_name("len").on_request(|_: &mut DummyString, Len(s)| s.len()).spawn().unwrap();
+
+    assert_eq!(group.ask("len", Len(String::from("Hello"))).unwrap(), 5);
+
+    let rep: String = group.send_to_with_reply("len", ()).unwrap();
+    assert_eq!(rep, "String");
+
+    assert!(matches!(group.ask("missing", Len(String::new())), Err(ClusterError::IdNotFound("missing"))));
+}
+
+#[test]
+fn lifecycle() {
+    struct Lifecycle(&'static str, crossbeam_channel::Sender<String>);
+
+    impl Unit for Lifecycle {
+        fn on_message(&mut self, _: Content) {}
+        fn on_message_with_reply(&mut self, _: Content) -> Content {
+            ().into_content()
+        }
+
+        fn on_start(&mut self) {
+            self.1.send(format!("{} start", self.0)).unwrap();
+        }
+
+        fn on_stop(&mut self, reason: StopReason) {
+            self.1.send(format!("{} stop {:?}", self.0, reason)).unwrap();
+        }
+    }
+
+    let (tx, rx) = crossbeam_channel::unbounded();
+    let mut group = Cluster::new();
+
+    group.register(Lifecycle("a", tx.clone())).with_name("a").spawn().unwrap();
+    group.register(Lifecycle("b", tx)).with_name("b").spawn().unwrap();
+
+    assert_eq!(rx.recv().unwrap(), "a start");
+    assert_eq!(rx.recv().unwrap(), "b start");
+
+    group.remove("a").unwrap();
+    assert_eq!(rx.recv().unwrap(), "a stop Removed");
+
+    drop(group);
+    assert_eq!(rx.recv().unwrap(), "b stop ClusterDropped");
+}
+
+#[test]
+fn panic_isolation() {
+    struct Fragile;
+
+    impl Unit for Fragile {
+        fn on_message(&mut self, _: Content) {
+            panic!("boom");
+        }
+
+        fn on_message_with_reply(&mut self, _: Content) -> Content {
+            panic!("boom");
+        }
+    }
+
+    let mut group = Cluster::new();
+    group.register(Fragile).with_name("fragile").spawn().unwrap();
+    group.register(Fragile).with_name("other").spawn().unwrap();
+    group.register(DummyI32).with_name("i32").spawn().unwrap();
+
+    let rep = group.send_to_with_reply::<_, i32>("fragile", ());
+    assert!(matches!(rep, Err(ClusterError::UnitPanicked(msg)) if msg == "boom"));
+
+    group.send_to("other", ()).unwrap();
+
+    let rep: i32 = group.send_to_with_reply("i32", ()).unwrap();
+    assert_eq!(rep, 1);
+}
+
+#[test]
+fn should_stop_panic() {
+    struct Fragile;
+
+    impl Unit for Fragile {
+        fn on_message(&mut self, _: Content) {}
+        fn on_message_with_reply(&mut self, _: Content) -> Content { ().into_content() }
+
+        fn should_stop(&self) -> bool {
+            panic!("boom");
+        }
+    }
+
+    let mut group = Cluster::new();
+    group.register(Fragile).with_name("fragile").spawn().unwrap();
+    group.register(DummyI32).with_name("i32").spawn().unwrap();
+
+    group.send_to_with_reply::<_, ()>("fragile", ()).unwrap();
+
+    let rep: i32 = group.send_to_with_reply("i32", ()).unwrap();
+    assert_eq!(rep, 1);
+    assert!(group.send_to_with_reply::<_, ()>("fragile", ()).is_err());
+}
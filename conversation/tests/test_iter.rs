@@ -0,0 +1,98 @@
+use std::thread;
+
+use conversation::Message;
+
+#[test]
+fn iter() {
+    let (tx, rx) = conversation::channel();
+
+    let handle = thread::spawn(move || {
+        for i in 0..10 {
+            tx.send_msg(i).unwrap();
+        }
+    });
+
+    let received = rx.iter().filter_map(|msg| msg.into::<i32>()).collect::<Vec<_>>();
+    assert_eq!(received, (0..10).collect::<Vec<_>>());
+
+    handle.join().unwrap();
+}
+
+#[test]
+fn into_iter() {
+    let (tx, rx) = conversation::channel();
+
+    tx.send_msg(1).unwrap();
+    tx.send_msg(2).unwrap();
+    drop(tx);
+
+    let mut count = 0;
+    for msg in &rx {
+        assert!(matches!(msg, Message::Simple(_)));
+        count += 1;
+    }
+    assert_eq!(count, 2);
+
+    let (tx, rx) = conversation::channel();
+
+    tx.send_msg(1).unwrap();
+    drop(tx);
+
+    assert_eq!(rx.into_iter().count(), 1);
+}
+
+#[test]
+fn try_iter() {
+    let (tx, rx) = conversation::channel();
+
+    assert_eq!(rx.try_iter().count(), 0);
+
+    tx.send_msg(1).unwrap();
+    tx.send_msg(2).unwrap();
+
+    assert_eq!(rx.try_iter().count(), 2);
+    assert_eq!(rx.try_iter().count(), 0);
+}
+
+#[test]
+fn iter_of() {
+    let (tx, rx) = conversation::channel();
+
+    tx.send_msg(1).unwrap();
+    tx.send_msg("Hello").unwrap();
+    tx.send_msg(2).unwrap();
+    tx.send_msg(String::from("World")).unwrap();
+    tx.send_msg(3).unwrap();
+    drop(tx);
+
+    let received = rx.iter_of::<i32>().collect::<Vec<_>>();
+    assert_eq!(received, vec![1, 2, 3]);
+}
+
+#[cfg(feature = "stream")]
+#[tokio::test]
+async fn stream() {
+    use std::future;
+    use std::pin::pin;
+
+    use futures_core::Stream;
+
+    let (tx, rx) = conversation::channel();
+
+    let handle = thread::spawn(move || {
+        for i in 0..10 {
+            tx.send_msg(i).unwrap();
+        }
+    });
+
+    let mut stream = pin!(rx.into_stream());
+    let mut received = Vec::new();
+
+    while let Some(msg) = future::poll_fn(|cx| stream.as_mut().poll_next(cx)).await {
+        received.push(msg.into::<i32>().unwrap());
+    }
+
+    assert_eq!(received, (0..10).collect::<Vec<_>>());
+
+    handle.join().unwrap();
+}
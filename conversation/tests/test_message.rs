@@ -1,4 +1,4 @@
-use conversation::{IntoContent, IntoMessage};
+use conversation::{Content, IntoContent, IntoMessage, Message, SendMessageError};
 
 #[test]
 fn create_content() {
@@ -12,10 +12,197 @@ fn create_content() {
 #[test]
 fn create_message() {
     let a = 1.into_msg();
-    let b = 2.into_msg_with_reply();
+    let (b, _reply) = 2.into_msg_with_reply();
     let c = "three".into_msg();
 
     assert_eq!(a.into::<i32>(), Some(1));
     assert_eq!(b.into::<String>(), None);
     assert_eq!(c.into::<&str>(), Some("three"));
 }
+
+#[test]
+fn content_type_name() {
+    let a = 1.into_content();
+    let b = String::from("two").into_content();
+
+    assert_eq!(a.type_name(), "i32");
+    assert_eq!(b.type_name(), std::any::type_name::<String>());
+
+    assert_eq!(format!("{:?}", a), "Content(i32)");
+    assert_eq!(format!("{:?}", "three".into_msg()), "Simple(Content(&str))");
+    assert_eq!(4u8.into_msg().type_name(), Some("u8"));
+}
+
+#[test]
+fn downcast() {
+    let a = 1.into_content();
+
+    let a = a.downcast::<String>().unwrap_err();
+    assert_eq!(a.type_name(), "i32");
+    assert_eq!(a.downcast::<i32>().unwrap(), 1);
+
+    let (b, _reply) = "two".into_msg_with_reply();
+
+    let b = b.downcast::<i32>().unwrap_err();
+    assert!(matches!(b, Message::WithReply(..)));
+    assert_eq!(b.downcast::<&str>().unwrap(), "two");
+
+    assert!(matches!(Message::Disconnect.downcast::<()>(), Err(Message::Disconnect)));
+}
+
+#[test]
+fn downcast_send_error() {
+    let (tx, rx) = conversation::channel();
+    drop(rx);
+
+    let err = tx.send_msg(3).unwrap_err();
+    let err: SendMessageError = err.downcast::<String>().unwrap_err();
+    assert_eq!(err.downcast::<i32>().unwrap(), 3);
+}
+
+#[test]
+fn match_content() {
+    #[derive(Debug, PartialEq)]
+    struct Wrapper<T>(T);
+
+    fn describe<T: Send + 'static>(msg: Content) -> String {
+        conversation::match_content!(msg, {
+            i32 => |val| format!("i32 {}", val),
+            Wrapper<T> => |Wrapper(_)| String::from("wrapper"),
+            &str => |val| val.to_string(),
+            _ => |other| format!("unknown {}", other.type_name())
+        })
+    }
+
+    assert_eq!(describe::<u8>(1.into_content()), "i32 1");
+    assert_eq!(describe::<u8>(Wrapper(2u8).into_content()), "wrapper");
+    assert_eq!(describe::<u8>("three".into_content()), "three");
+    assert_eq!(describe::<u8>(4u64.into_content()), "unknown u64");
+
+    let mut seen = 0;
+    conversation::match_content!(5.into_content(), {
+        i32 => |val| seen += val
+    });
+    conversation::match_content!(6u8.into_content(), {
+        i32 => |val| seen += val,
+    });
+    assert_eq!(seen, 5);
+}
+
+#[test]
+fn match_content_reply() {
+    fn reply(msg: Content) -> Content {
+        conversation::match_content!(reply msg, {
+            i32 => |val| val * 2,
+            String => |val| val.len(),
+            _ => |other| other
+        })
+    }
+
+    assert_eq!(reply(2.into_content()).into::<i32>(), Some(4));
+    assert_eq!(reply(String::from("four").into_content()).into::<usize>(), Some(4));
+    assert_eq!(reply("echo".into_content()).into::<&str>(), Some("echo"));
+
+    let unit = conversation::match_content!(reply 1u8.into_content(), {
+        i32 => |val| val
+    });
+    assert!(unit.is::<()>());
+}
+
+#[test]
+fn shared_content() {
+    let a = Content::shared(vec![1u8, 2, 3]);
+    let b = a.share().unwrap();
+
+    assert!(a.is_shared());
+    assert!(a.is::<Vec<u8>>());
+    assert_eq!(a.type_name(), std::any::type_name::<Vec<u8>>());
+    assert_eq!(a.as_ref::<Vec<u8>>().unwrap().as_ptr(), b.as_ref::<Vec<u8>>().unwrap().as_ptr());
+
+    let a = a.try_unwrap::<Vec<u8>>().unwrap_err();
+    let a = a.downcast::<String>().unwrap_err();
+    assert_eq!(a.into_arc::<Vec<u8>>().unwrap().len(), 3);
+
+    assert_eq!(b.try_unwrap::<Vec<u8>>().unwrap(), vec![1, 2, 3]);
+
+    let a = Content::shared(String::from("copy"));
+    let b = a.share().unwrap();
+    assert_eq!(a.into::<String>().as_deref(), Some("copy"));
+    assert_eq!(b.into::<String>().as_deref(), Some("copy"));
+
+    let a = Content::from_arc(std::sync::Arc::new(5u64));
+    let b = a.share().unwrap();
+    assert!(a.downcast::<u64>().is_err());
+    assert_eq!(b.into::<u64>(), Some(5));
+
+    assert!(1.into_content().share().is_none());
+    assert_eq!(*2.into_content().into_arc::<i32>().unwrap(), 2);
+}
+
+#[test]
+fn content_is_not_nested() {
+    let a = Content::from(1.into_content());
+    assert!(a.is::<i32>());
+    assert!(!a.is::<Content>());
+    assert_eq!(a.type_name(), "i32");
+
+    let b = Content::shared(vec![1u8]).into_content();
+    assert!(b.is_shared());
+    assert!(b.into::<Content>().is_none());
+
+    let msg = String::from("text").into_content().into_msg();
+    assert_eq!(msg.into::<String>().as_deref(), Some("text"));
+
+    let (tx, rx) = conversation::channel();
+    tx.send_msg(2u8.into_content()).unwrap();
+    assert_eq!(rx.recv_msg().unwrap().into::<u8>(), Some(2));
+}
+
+#[test]
+fn send_content() {
+    let (tx, rx) = conversation::channel();
+
+    tx.send_msg(1.into_content()).unwrap();
+    tx.send_msg(Content::shared(String::from("shared"))).unwrap();
+
+    assert_eq!(rx.recv_msg().unwrap().into::<i32>(), Some(1));
+    assert_eq!(rx.recv_msg().unwrap().into::<String>().as_deref(), Some("shared"));
+}
+
+#[test]
+fn small_content() {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct Tracked(Arc<AtomicUsize>);
+
+    impl Drop for Tracked {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    let drops = Arc::new(AtomicUsize::new(0));
+
+    let cont = Tracked(Arc::clone(&drops)).into_content();
+    assert!(cont.is::<Tracked>());
+    let cont = cont.downcast::<u64>().unwrap_err();
+    drop(cont);
+    assert_eq!(drops.load(Ordering::SeqCst), 1);
+
+    let tracked = Tracked(Arc::clone(&drops)).into_content().into::<Tracked>().unwrap();
+    assert_eq!(drops.load(Ordering::SeqCst), 1);
+    drop(tracked);
+    assert_eq!(drops.load(Ordering::SeqCst), 2);
+
+    let cont = (1u8, 2u32, 3u64).into_content();
+    assert_eq!(cont.as_ref::<(u8, u32, u64)>(), Some(&(1, 2, 3)));
+    assert_eq!(cont.into(), Some((1u8, 2u32, 3u64)));
+
+    let large = [7u64; 16].into_content();
+    assert_eq!(large.as_ref::<[u64; 16]>().map(|arr| arr[15]), Some(7));
+    assert_eq!(large.into::<[u64; 16]>(), Some([7; 16]));
+
+    assert_eq!(*5u16.into_content().into_arc::<u16>().unwrap(), 5);
+    assert_eq!(().into_content().type_name(), "()");
+}
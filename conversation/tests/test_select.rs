@@ -0,0 +1,71 @@
+use std::thread;
+use std::time::Duration;
+
+use conversation::{Message, Select, SelectTimeoutError, TrySelectError};
+
+#[test]
+fn select_receivers() {
+    let (tx1, rx1) = conversation::channel();
+    let (tx2, rx2) = conversation::channel();
+
+    let mut sel = Select::new();
+    let idx1 = sel.recv(&rx1);
+    let idx2 = sel.recv(&rx2);
+
+    assert_eq!(sel.try_ready(), Err(TrySelectError));
+    assert_eq!(sel.ready_timeout(Duration::from_millis(10)), Err(SelectTimeoutError));
+
+    tx2.send_msg(2).unwrap();
+    assert_eq!(sel.ready(), idx2);
+    assert_eq!(rx2.try_recv_msg().unwrap().into(), Some(2));
+
+    tx1.send_msg(1).unwrap();
+    assert_eq!(sel.ready_timeout(Duration::from_secs(5)), Ok(idx1));
+    assert_eq!(rx1.try_recv_msg().unwrap().into(), Some(1));
+
+    sel.remove(idx1);
+    tx1.send_msg(1).unwrap();
+    assert_eq!(sel.try_ready(), Err(TrySelectError));
+}
+
+#[test]
+fn select_inbox_and_replies() {
+    let (tx, rx) = conversation::channel();
+    let (peer_tx, peer_rx) = conversation::channel();
+    let (go_tx, go_rx) = conversation::channel();
+
+    let handle = thread::spawn(move || {
+        if let Message::WithReply(data, reply_to) = peer_rx.recv_msg().unwrap() {
+            thread::sleep(Duration::from_millis(10));
+            reply_to.send(data).unwrap();
+        }
+
+        go_rx.recv_msg().unwrap();
+
+        if let Message::WithReply(data, reply_to) = peer_rx.recv_msg().unwrap() {
+            reply_to.send(data).unwrap();
+        }
+    });
+
+    let reply = peer_tx.send_msg_with_reply(1).unwrap();
+    let pending = peer_tx.request::<_, i32>(2).unwrap();
+
+    let mut sel = Select::new();
+    let inbox = sel.recv(&rx);
+    let first = sel.reply(&reply);
+    let second = sel.pending(&pending);
+
+    assert_eq!(sel.ready(), first);
+    assert_eq!(reply.try_recv().unwrap().into(), Some(1));
+    sel.remove(first);
+
+    go_tx.send_msg(()).unwrap();
+    assert_eq!(sel.ready(), second);
+    assert_eq!(pending.try_get().unwrap(), 2);
+    sel.remove(second);
+
+    tx.send_msg(()).unwrap();
+    assert_eq!(sel.ready(), inbox);
+
+    handle.join().unwrap();
+}
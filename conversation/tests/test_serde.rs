@@ -0,0 +1,78 @@
+#![cfg(feature = "serde")]
+
+use serde::{Deserialize, Serialize};
+
+use conversation::{Content, IntoContent, IntoMessage, Message, RegisterTypeError};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Point {
+    x: i32,
+    y: i32
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Unregistered;
+
+fn register() {
+    conversation::register_type::<Point>("point").unwrap();
+    conversation::register_type::<String>("string").unwrap();
+}
+
+#[test]
+fn content_json() {
+    register();
+
+    let cont = Point { x: 1, y: 2 }.into_content();
+    assert_eq!(cont.tag(), Some("point"));
+
+    let json = serde_json::to_string(&cont).unwrap();
+    assert_eq!(json, r#"["point",{"x":1,"y":2}]"#);
+
+    let cont: Content = serde_json::from_str(&json).unwrap();
+    assert_eq!(cont.type_name(), std::any::type_name::<Point>());
+    assert_eq!(cont.into::<Point>(), Some(Point { x: 1, y: 2 }));
+}
+
+#[test]
+fn content_bincode() {
+    register();
+
+    let bytes = bincode::serialize(&String::from("Hello").into_content()).unwrap();
+    let cont: Content = bincode::deserialize(&bytes).unwrap();
+
+    assert_eq!(cont.into::<String>().as_deref(), Some("Hello"));
+}
+
+#[test]
+fn message() {
+    register();
+
+    let bytes = bincode::serialize(&Point { x: 3, y: 4 }.into_msg()).unwrap();
+    let msg: Message = bincode::deserialize(&bytes).unwrap();
+    assert_eq!(msg.into::<Point>(), Some(Point { x: 3, y: 4 }));
+
+    let json = serde_json::to_string(&Message::Disconnect).unwrap();
+    assert!(matches!(serde_json::from_str(&json).unwrap(), Message::Disconnect));
+
+    let (msg, _reply) = Point { x: 5, y: 6 }.into_msg_with_reply();
+    assert!(serde_json::to_string(&msg).is_err());
+}
+
+#[test]
+fn unregistered() {
+    register();
+
+    assert!(!conversation::is_registered::<Unregistered>());
+    assert_eq!(Unregistered.into_content().tag(), None);
+
+    let err = serde_json::to_string(&Unregistered.into_content()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!("type {} is not registered for serialization", std::any::type_name::<Unregistered>())
+    );
+
+    let err = serde_json::from_str::<Content>(r#"["unknown",null]"#).unwrap_err();
+    assert!(err.to_string().starts_with("tag unknown is not registered for deserialization"));
+
+    assert_eq!(conversation::register_type::<Unregistered>("point"), Err(RegisterTypeError("point")));
+}
@@ -0,0 +1,197 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use conversation::{Cluster, ClusterError, Content, IntoContent, MessageSender, ReplyError, Strategy, Supervisor, SupervisorError, Unit};
+
+struct Counter(i32);
+
+impl Unit for Counter {
+    fn on_message(&mut self, _: Content) {}
+    fn on_message_with_reply(&mut self, data: Content) -> Content {
+        if data.into::<&str>() == Some("boom") {
+            panic!("boom");
+        }
+
+        self.0 += 1;
+        self.0.into_content()
+    }
+}
+
+fn counter(created: &Arc<AtomicUsize>) -> impl FnMut() -> Counter + Send + 'static {
+    let created = Arc::clone(created);
+
+    move || {
+        created.fetch_add(1, Ordering::SeqCst);
+        Counter(0)
+    }
+}
+
+fn ask(sender: &MessageSender) -> Result<i32, ReplyError> {
+    sender.request::<_, i32>(()).unwrap().wait()
+}
+
+fn crash(sender: &MessageSender) {
+    let reply = sender.request::<_, i32>("boom").unwrap().wait();
+    assert_eq!(reply, Err(ReplyError::UnitPanicked(String::from("boom"))));
+}
+
+fn wait_stopped(sup: &Supervisor) {
+    let deadline = Instant::now() + Duration::from_secs(5);
+
+    while !sup.is_stopped() {
+        assert!(Instant::now() < deadline);
+        thread::sleep(Duration::from_millis(1));
+    }
+}
+
+#[test]
+fn one_for_one() {
+    let (a_created, b_created) = (Arc::new(AtomicUsize::new(0)), Arc::new(AtomicUsize::new(0)));
+    let mut sup = Supervisor::new(Strategy::OneForOne);
+
+    let a = sup.spawn_pipe("a", counter(&a_created)).unwrap();
+    let b = sup.spawn_pipe("b", counter(&b_created)).unwrap();
+
+    assert!(matches!(sup.spawn_pipe("a", || Counter(0)), Err(SupervisorError::NameAlreadyUsed("a"))));
+
+    assert_eq!(ask(&a), Ok(1));
+    assert_eq!(ask(&b), Ok(1));
+
+    crash(&a);
+
+    assert_eq!(ask(&a), Ok(1));
+    assert_eq!(ask(sup.sender("a").unwrap()), Ok(2));
+    assert_eq!(ask(&b), Ok(2));
+
+    assert_eq!(a_created.load(Ordering::SeqCst), 2);
+    assert_eq!(b_created.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn one_for_all() {
+    let (a_created, b_created) = (Arc::new(AtomicUsize::new(0)), Arc::new(AtomicUsize::new(0)));
+    let mut sup = Supervisor::new(Strategy::OneForAll);
+
+    let b = sup.spawn_pipe("b", counter(&b_created)).unwrap();
+    let a = sup.spawn_pipe("a", counter(&a_created)).unwrap();
+
+    assert_eq!(ask(&b), Ok(1));
+
+    crash(&a);
+
+    assert_eq!(ask(&a), Ok(1));
+    assert_eq!(ask(&b), Ok(1));
+
+    assert_eq!(a_created.load(Ordering::SeqCst), 2);
+    assert_eq!(b_created.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn rest_for_one() {
+    let created: Vec<_> = (0..3).map(|_| Arc::new(AtomicUsize::new(0))).collect();
+    let mut sup = Supervisor::new(Strategy::RestForOne);
+
+    let before = sup.spawn_pipe("before", counter(&created[0])).unwrap();
+    let failing = sup.spawn_pipe("failing", counter(&created[1])).unwrap();
+    let after = sup.spawn_pipe("after", counter(&created[2])).unwrap();
+
+    crash(&failing);
+
+    assert_eq!(ask(&failing), Ok(1));
+    assert_eq!(ask(&before), Ok(1));
+    assert_eq!(ask(&after), Ok(1));
+
+    let created: Vec<_> = created.iter().map(|count| count.load(Ordering::SeqCst)).collect();
+    assert_eq!(created, vec![1, 2, 2]);
+}
+
+#[test]
+fn escalate_without_parent() {
+    let created = Arc::new(AtomicUsize::new(0));
+    let mut sup = Supervisor::new(Strategy::OneForOne).with_intensity(1, Duration::from_secs(60));
+
+    let a = sup.spawn_pipe("a", counter(&created)).unwrap();
+
+    crash(&a);
+    assert_eq!(ask(&a), Ok(1));
+
+    crash(&a);
+    wait_stopped(&sup);
+
+    assert!(a.send_msg(()).is_err());
+    assert!(matches!(sup.spawn_pipe("b", || Counter(0)), Err(SupervisorError::Stopped)));
+    assert_eq!(created.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn escalate_to_parent() {
+    let created = Arc::new(AtomicUsize::new(0));
+
+    let mut child = Supervisor::new(Strategy::OneForOne).with_intensity(0, Duration::from_secs(60));
+    let leaf = child.spawn_pipe("leaf", counter(&created)).unwrap();
+
+    let mut parent = Supervisor::new(Strategy::OneForOne);
+    parent.add_supervisor("child", child).unwrap();
+
+    assert_eq!(ask(&leaf), Ok(1));
+
+    crash(&leaf);
+
+    assert_eq!(ask(&leaf), Ok(1));
+    assert_eq!(created.load(Ordering::SeqCst), 2);
+    assert!(!parent.is_stopped());
+}
+
+#[test]
+fn add_stopped_supervisor() {
+    let mut child = Supervisor::new(Strategy::OneForOne).with_intensity(0, Duration::from_secs(60));
+    let leaf = child.spawn_pipe("leaf", || Counter(0)).unwrap();
+
+    crash(&leaf);
+    wait_stopped(&child);
+
+    let mut parent = Supervisor::new(Strategy::OneForAll);
+    let other = parent.spawn_pipe("other", || Counter(0)).unwrap();
+    parent.add_supervisor("child", child).unwrap();
+
+    crash(&other);
+    assert_eq!(ask(&other), Ok(1));
+
+    let (done, dropped) = crossbeam_channel::bounded(1);
+
+    thread::spawn(move || {
+        drop(parent);
+        let _ = done.send(());
+    });
+
+    assert!(dropped.recv_timeout(Duration::from_secs(3)).is_ok());
+}
+
+#[test]
+fn in_cluster() {
+    let created = Arc::new(AtomicUsize::new(0));
+    let mut group = Cluster::new();
+    let mut sup = Supervisor::new(Strategy::OneForOne);
+
+    sup.spawn_in(&mut group, "counter", counter(&created)).unwrap();
+    group.register(Counter(0)).with_name("other").spawn().unwrap();
+
+    assert!(matches!(sup.spawn_in(&mut group, "other", || Counter(0)), Err(SupervisorError::ClusterError(ClusterError::IdAlreadyUsed("other")))));
+    assert!(sup.sender("counter").is_none());
+
+    let rep: i32 = group.send_to_with_reply("counter", ()).unwrap();
+    assert_eq!(rep, 1);
+
+    let rep = group.send_to_with_reply::<_, i32>("counter", "boom");
+    assert!(matches!(rep, Err(ClusterError::UnitPanicked(msg)) if msg == "boom"));
+
+    let rep: i32 = group.send_to_with_reply("counter", ()).unwrap();
+    assert_eq!(rep, 1);
+
+    let rep: i32 = group.send_to_with_reply("other", ()).unwrap();
+    assert_eq!(rep, 1);
+
+    assert_eq!(created.load(Ordering::SeqCst), 2);
+}
@@ -0,0 +1,174 @@
+use std::sync::{Arc, Mutex};
+
+use conversation::{Cluster, ClusterError, Content, ErrorPolicy, IntoContent, StopReason, TryReplyError, TryUnit};
+
+#[derive(Debug, PartialEq)]
+enum AccError {
+    Negative(i32),
+    NotANumber
+}
+
+struct Acc(i32);
+
+impl Acc {
+    fn add(&mut self, data: Content) -> Result<i32, AccError> {
+        match data.into::<i32>() {
+            Some(val) if val < 0 => Err(AccError::Negative(val)),
+            Some(val) => {
+                self.0 += val;
+                Ok(self.0)
+            }
+            None => Err(AccError::NotANumber)
+        }
+    }
+}
+
+impl TryUnit for Acc {
+    type Error = AccError;
+
+    fn try_on_message(&mut self, data: Content) -> Result<(), AccError> {
+        self.add(data).map(|_| ())
+    }
+
+    fn try_on_message_with_reply(&mut self, data: Content) -> Result<Content, AccError> {
+        self.add(data).map(IntoContent::into_content)
+    }
+}
+
+#[test]
+fn log_and_continue() {
+    let pipe = Acc(0).build_try().spawn_try_pipe();
+
+    assert_eq!(pipe.send_with_reply::<_, i32>(2).unwrap(), 2);
+    assert!(matches!(pipe.send_with_reply::<_, i32>(-1), Err(TryReplyError::Failed(AccError::Negative(-1)))));
+    assert!(matches!(pipe.send_with_reply::<_, i32>("two"), Err(TryReplyError::Failed(AccError::NotANumber))));
+
+    pipe.send(-5).unwrap();
+    assert_eq!(pipe.sender().send_with_reply::<_, i32>(3).unwrap(), 5);
+
+    assert!(matches!(pipe.send_with_reply::<_, String>(1), Err(TryReplyError::Reply(_))));
+}
+
+#[test]
+fn stop_on_error() {
+    let pipe = Acc(0).build_try().with_error_policy(ErrorPolicy::Stop).spawn_try_pipe();
+
+    assert_eq!(pipe.send_with_reply::<_, i32>(1).unwrap(), 1);
+    assert!(matches!(pipe.send_with_reply::<_, i32>(-1), Err(TryReplyError::Failed(AccError::Negative(-1)))));
+    assert!(matches!(pipe.send_with_reply::<_, i32>(1), Err(TryReplyError::Reply(_))));
+}
+
+#[test]
+fn restart_on_error() {
+    let pipe = Acc(100).build_try().with_error_policy(ErrorPolicy::restart(|| Acc(0))).spawn_try_pipe();
+
+    assert_eq!(pipe.send_with_reply::<_, i32>(1).unwrap(), 101);
+
+    pipe.send("one").unwrap();
+    assert_eq!(pipe.send_with_reply::<_, i32>(1).unwrap(), 1);
+
+    assert!(pipe.send_with_reply::<_, i32>(-1).is_err());
+    assert_eq!(pipe.send_with_reply::<_, i32>(2).unwrap(), 2);
+}
+
+#[test]
+fn error_sink() {
+    let (sink, errors) = conversation::channel();
+    let pipe = Acc(0).build_try().with_error_policy(ErrorPolicy::Sink(sink)).spawn_try_pipe();
+
+    pipe.send(-1).unwrap();
+    pipe.send("one").unwrap();
+    assert!(matches!(pipe.send_with_reply::<_, i32>(-2), Err(TryReplyError::Failed(AccError::Negative(-2)))));
+
+    assert_eq!(errors.recv_msg().unwrap().into::<AccError>(), Some(AccError::Negative(-1)));
+    assert_eq!(errors.recv_msg().unwrap().into::<AccError>(), Some(AccError::NotANumber));
+    assert!(errors.try_recv_msg().is_err());
+}
+
+#[test]
+fn try_in_cluster() {
+    let mut group = Cluster::new();
+
+    let acc = group.register_try(Acc(0)).with_name("acc").spawn_try().unwrap();
+
+    assert_eq!(acc.send_with_reply::<_, i32>(4).unwrap(), 4);
+    assert!(matches!(acc.send_with_reply::<_, i32>(-4), Err(TryReplyError::Failed(AccError::Negative(-4)))));
+
+    let rep: i32 = group.send_to_with_reply("acc", 1).unwrap();
+    assert_eq!(rep, 5);
+
+    let fragile = group.register_try(Acc(0)).with_name("fragile").with_error_policy(ErrorPolicy::Stop).spawn_try().unwrap();
+
+    assert!(fragile.send_with_reply::<_, i32>("one").is_err());
+    assert!(matches!(fragile.send_with_reply::<_, i32>(1), Err(TryReplyError::Reply(_))));
+    assert_eq!(acc.send_with_reply::<_, i32>(1).unwrap(), 6);
+}
+
+#[test]
+fn named_sink() {
+    let mut group = Cluster::new();
+    let (sink, errors) = conversation::channel();
+
+    let missing = group.register_try(Acc(0)).with_name("acc").with_error_sink("errors");
+    assert!(matches!(missing, Err(ClusterError::IdNotFound("errors"))));
+    assert!(matches!(Acc(0).build_try().with_error_sink("errors"), Err(ClusterError::RegistrationError)));
+
+    struct Forward(conversation::MessageSender);
+
+    impl conversation::Unit for Forward {
+        fn on_message(&mut self, data: Content) {
+            self.0.send_msg(data).unwrap();
+        }
+
+        fn on_message_with_reply(&mut self, data: Content) -> Content { data }
+    }
+
+    group.register(Forward(sink)).with_name("errors").spawn().unwrap();
+
+    let acc = group.register_try(Acc(0)).with_name("acc").with_error_sink("errors").unwrap().spawn_try().unwrap();
+    group.register_try(Acc(0)).with_name("plain").with_error_sink("errors").unwrap().spawn().unwrap();
+
+    acc.send(-3).unwrap();
+    assert_eq!(errors.recv_msg().unwrap().into::<AccError>(), Some(AccError::Negative(-3)));
+
+    group.send_to("plain", "four").unwrap();
+    assert_eq!(errors.recv_msg().unwrap().into::<AccError>(), Some(AccError::NotANumber));
+}
+
+#[test]
+fn lifecycle() {
+    struct Tracked(Arc<Mutex<Vec<String>>>);
+
+    impl TryUnit for Tracked {
+        type Error = ();
+
+        fn try_on_message(&mut self, _: Content) -> Result<(), ()> {
+            Err(())
+        }
+
+        fn try_on_message_with_reply(&mut self, _: Content) -> Result<Content, ()> {
+            Ok(().into_content())
+        }
+
+        fn on_start(&mut self) {
+            self.0.lock().unwrap().push(String::from("start"));
+        }
+
+        fn on_stop(&mut self, reason: StopReason) {
+            self.0.lock().unwrap().push(format!("stop {:?}", reason));
+        }
+    }
+
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let factory_log = Arc::clone(&log);
+
+    let pipe = Tracked(Arc::clone(&log)).build_try()
+        .with_error_policy(ErrorPolicy::restart(move || Tracked(Arc::clone(&factory_log))))
+        .spawn_try_pipe();
+
+    pipe.send(()).unwrap();
+    pipe.send_with_reply::<_, ()>(()).unwrap();
+    drop(pipe);
+
+    assert_eq!(*log.lock().unwrap(), ["start", "stop Restart", "start", "stop Disconnected"]);
+}
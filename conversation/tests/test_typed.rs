@@ -0,0 +1,80 @@
+use std::thread;
+
+use conversation::{Cluster, Content, IntoContent, TypedMessage, TypedUnit, Unit};
+
+struct Adder(i32);
+
+impl TypedUnit for Adder {
+    type Message = i32;
+    type Reply = i32;
+
+    fn handle(&mut self, msg: i32) -> i32 {
+        self.0 += msg;
+        self.0
+    }
+}
+
+struct Erased;
+
+impl Unit for Erased {
+    fn on_message(&mut self, _: Content) {}
+    fn on_message_with_reply(&mut self, _: Content) -> Content {
+        "erased".into_content()
+    }
+}
+
+#[test]
+fn typed_channel() {
+    let (tx, rx) = conversation::typed_channel::<String, usize>();
+
+    let handle = thread::spawn(move || {
+        while let Ok(msg) = rx.recv() {
+            match msg {
+                TypedMessage::Simple(_) => {}
+                TypedMessage::WithReply(msg, reply_to) => reply_to.send(msg.len()).unwrap(),
+                TypedMessage::Disconnect => return
+            }
+        }
+    });
+
+    tx.send(String::from("ignored")).unwrap();
+    assert_eq!(tx.send_with_reply(String::from("Hello")).unwrap(), 5);
+
+    let pending = tx.request(String::from("Hi")).unwrap();
+    assert_eq!(pending.wait().unwrap(), 2);
+
+    drop(tx);
+    handle.join().unwrap();
+}
+
+#[test]
+fn typed_pipe() {
+    let pipe = Adder(0).build_typed().spawn_typed_pipe();
+
+    pipe.send(1).unwrap();
+    assert_eq!(pipe.send_with_reply(2).unwrap(), 3);
+
+    let sender = pipe.sender();
+    assert_eq!(sender.request(3).unwrap().wait().unwrap(), 6);
+
+    let erased: i32 = pipe.as_erased().send_with_reply(4).unwrap();
+    assert_eq!(erased, 10);
+}
+
+#[test]
+fn typed_in_cluster() {
+    let mut group = Cluster::new();
+
+    let adder = group.register_typed(Adder(0)).with_name("adder").spawn_typed().unwrap();
+    group.register(Erased).with_name("erased").spawn().unwrap();
+
+    assert_eq!(adder.send_with_reply(1).unwrap(), 1);
+
+    let rep: i32 = group.send_to_with_reply("adder", 2).unwrap();
+    assert_eq!(rep, 3);
+
+    let rep: &str = group.send_to_with_reply("erased", ()).unwrap();
+    assert_eq!(rep, "erased");
+
+    assert!(group.register_typed(Adder(0)).with_name("erased").spawn_typed().is_err());
+}
@@ -1,5 +1,8 @@
 use std::any::Any;
-use conversation::{Content, IntoContent, WithContext, Unit, MessageSender};
+use std::sync::{Arc, Barrier, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use conversation::{Content, Envelope, Handler, IntoContent, WithContext, Unit, MessageSender, Priority, ReplyError, Request, StopReason};
 
 struct Dummy;
 
@@ -38,6 +41,15 @@ impl<T: Any + Send + 'static> Unit for Type<T> {
     }
 }
 
+struct Echo;
+
+impl Unit for Echo {
+    fn on_message(&mut self, _: Content) {}
+    fn on_message_with_reply(&mut self, data: Content) -> Content {
+        data
+    }
+}
+
 #[test]
 fn build() {
     let _ = Dummy.build_unit().spawn_pipe();
@@ -45,6 +57,40 @@ fn build() {
     let _ = Type::<i32>::default().build_unit().spawn_pipe();
 }
 
+#[test]
+fn build_bounded() {
+    let msger = Type::<i32>::default().build_unit().with_capacity(1).spawn_pipe();
+
+    for i in 0..10 {
+        msger.send(i).unwrap();
+    }
+
+    let reply: Result<(), ()> = msger.send_with_reply(0).unwrap();
+    assert!(reply.is_ok());
+
+    let msger = DummyWithChannel.build_unit().with_capacity(4).with_context().spawn_pipe();
+    msger.try_send(0).unwrap();
+}
+
+#[test]
+fn build_rendezvous() {
+    let msger = Type::<i32>::default().build_unit().with_capacity(0).spawn_pipe();
+
+    for i in 0..10 {
+        msger.send(i).unwrap();
+    }
+
+    let deadline = Instant::now() + Duration::from_secs(5);
+
+    while let Err(err) = msger.try_send(10) {
+        assert!(err.is_full() && Instant::now() < deadline);
+        thread::sleep(Duration::from_millis(1));
+    }
+
+    let reply: Result<(), ()> = msger.send_with_reply(0).unwrap();
+    assert!(reply.is_ok());
+}
+
 #[test]
 fn send() {
     let msger = Type::<i32>::default().build_unit().spawn_pipe();
@@ -56,4 +102,439 @@ fn send() {
 
     let reply: Result<(), ()> = msger.send_with_reply("hello").unwrap();
     assert!(reply.is_err());
+
+    let err = msger.send_with_reply::<_, String>(0).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!("failed to convert Content: expected {}, found core::result::Result<(), ()>", std::any::type_name::<String>())
+    );
+}
+
+#[test]
+fn send_with_reply_from_many_threads() {
+    let msger = Arc::new(Echo.build_unit().spawn_pipe());
+
+    let callers = (0..8usize).map(|idx| {
+        let msger = Arc::clone(&msger);
+
+        thread::spawn(move || {
+            for i in 0..100 {
+                let reply: usize = msger.send_with_reply(idx * 1000 + i).unwrap();
+                assert_eq!(reply, idx * 1000 + i);
+            }
+        })
+    }).collect::<Vec<_>>();
+
+    callers.into_iter().for_each(|caller| caller.join().unwrap());
+}
+
+#[test]
+fn request() {
+    let pipes = (0..4usize).map(|_| Echo.build_unit().spawn_pipe()).collect::<Vec<_>>();
+
+    let pending = pipes.iter()
+        .enumerate()
+        .map(|(idx, pipe)| pipe.request::<_, usize>(idx).unwrap())
+        .collect::<Vec<_>>();
+
+    for (idx, reply) in pending.into_iter().enumerate() {
+        assert_eq!(reply.wait().unwrap(), idx);
+    }
+
+    let reply = pipes[0].request::<_, usize>(10usize).unwrap();
+    assert_eq!(reply.wait_timeout(Duration::from_secs(5)).unwrap(), 10);
+
+    let reply = pipes[0].request::<_, usize>(11usize).unwrap();
+    while !reply.is_ready() {
+        thread::yield_now();
+    }
+    assert_eq!(reply.try_get().unwrap(), 11);
+    assert_eq!(reply.try_get().err(), Some(ReplyError::Disconnected));
+
+    let reply = pipes[0].request::<_, String>(12usize).unwrap();
+    match reply.wait() {
+        Err(ReplyError::ConvertContentError(err)) => {
+            assert_eq!(err.expected, std::any::type_name::<String>());
+            assert_eq!(err.found, "usize");
+        }
+        _ => panic!("expected a conversion error")
+    }
+}
+
+#[test]
+fn request_not_ready() {
+    struct Slow;
+
+    impl Unit for Slow {
+        fn on_message(&mut self, _: Content) {}
+        fn on_message_with_reply(&mut self, data: Content) -> Content {
+            thread::sleep(Duration::from_millis(50));
+            data
+        }
+    }
+
+    let msger = Slow.build_unit().spawn_pipe();
+    let reply = msger.request::<_, i32>(1).unwrap();
+
+    assert!(!reply.is_ready());
+    assert_eq!(reply.try_get().err(), Some(ReplyError::NotReady));
+    assert_eq!(reply.wait_timeout(Duration::from_millis(1)).err(), Some(ReplyError::Timeout));
+    assert_eq!(reply.wait().unwrap(), 1);
+}
+
+#[test]
+fn send_priority() {
+    struct Recorder(Arc<Barrier>, Arc<Mutex<Vec<i32>>>);
+
+    impl Unit for Recorder {
+        fn on_message(&mut self, data: Content) {
+            if data.is::<()>() {
+                self.0.wait();
+            } else if let Some(val) = data.into::<i32>() {
+                self.1.lock().unwrap().push(val);
+            }
+        }
+
+        fn on_message_with_reply(&mut self, _: Content) -> Content {
+            ().into_content()
+        }
+    }
+
+    let barrier = Arc::new(Barrier::new(2));
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let msger = Recorder(Arc::clone(&barrier), Arc::clone(&received)).build_unit().spawn_pipe();
+
+    msger.send(()).unwrap();
+    msger.send_priority(Priority::Low, 1).unwrap();
+    msger.send(2).unwrap();
+    msger.send_priority(Priority::Urgent, 3).unwrap();
+    msger.send_priority(Priority::High, 4).unwrap();
+    barrier.wait();
+
+    drop(msger);
+    assert_eq!(*received.lock().unwrap(), vec![3, 4, 2, 1]);
+}
+
+#[test]
+fn pipe_stats() {
+    #[derive(Default)]
+    struct Keeper(Option<MessageSender>);
+
+    impl Unit for Keeper {
+        fn on_message(&mut self, _: Content) {}
+        fn on_message_with_reply(&mut self, _: Content) -> Content {
+            ().into_content()
+        }
+    }
+
+    impl WithContext for Keeper {
+        fn with_context(&mut self, channel: MessageSender) {
+            self.0 = Some(channel)
+        }
+    }
+
+    let msger = Keeper::default().build_unit().with_capacity(8).with_context().with_stats().spawn_pipe();
+
+    assert_eq!(msger.capacity(), Some(8));
+    assert_eq!(msger.sender_count(), 2);
+    assert_eq!(msger.receiver_count(), 1);
+
+    msger.send(0).unwrap();
+    let _: () = msger.send_with_reply(0).unwrap();
+
+    assert!(msger.is_empty());
+    assert_eq!(msger.len(), 0);
+
+    let stats = msger.stats().unwrap();
+    assert_eq!(stats.sent, 2);
+    assert_eq!(stats.replies_sent, 1);
+    assert!(stats.high_water_mark >= 1);
+
+    let msger = Dummy.build_unit().spawn_pipe();
+    assert_eq!(msger.stats(), None);
+}
+
+#[test]
+fn envelope() {
+    #[derive(Default)]
+    struct Headers(Option<String>);
+
+    impl Unit for Headers {
+        fn on_message(&mut self, _: Content) {}
+        fn on_message_with_reply(&mut self, _: Content) -> Content {
+            self.0.take().into_content()
+        }
+
+        fn on_envelope(&mut self, envelope: &Envelope) {
+            self.0 = envelope.header("user").map(String::from)
+        }
+    }
+
+    let msger = Headers::default().build_unit().spawn_pipe();
+
+    let envelope = Envelope::new().with_header("user", "alice").with_sender("test");
+    assert_eq!(envelope.headers().collect::<Vec<_>>(), vec![("user", "alice")]);
+    assert!(envelope.timestamp() <= std::time::SystemTime::now());
+
+    let reply = msger.request_with_envelope::<_, Option<String>>(envelope, ()).unwrap();
+    assert_eq!(reply.wait().unwrap().as_deref(), Some("alice"));
+
+    let reply: Option<String> = msger.send_with_reply(()).unwrap();
+    assert_eq!(reply, None);
+}
+
+#[test]
+fn expired() {
+    struct Counter(Arc<Barrier>, Arc<Mutex<Vec<i32>>>);
+
+    impl Unit for Counter {
+        fn on_message(&mut self, data: Content) {
+            if data.is::<()>() {
+                self.0.wait();
+            } else if let Some(val) = data.into::<i32>() {
+                self.1.lock().unwrap().push(val);
+            }
+        }
+
+        fn on_message_with_reply(&mut self, data: Content) -> Content {
+            data
+        }
+    }
+
+    let barrier = Arc::new(Barrier::new(2));
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let msger = Counter(Arc::clone(&barrier), Arc::clone(&received)).build_unit().with_stats().spawn_pipe();
+
+    msger.send(()).unwrap();
+    msger.send_deadline(1, Instant::now() + Duration::from_millis(10)).unwrap();
+    msger.send_deadline(2, Instant::now() + Duration::from_secs(60)).unwrap();
+    let reply = msger.request_deadline::<_, i32>(3, Instant::now() + Duration::from_millis(10)).unwrap();
+
+    thread::sleep(Duration::from_millis(20));
+    barrier.wait();
+
+    assert_eq!(reply.wait().err(), Some(ReplyError::Expired));
+
+    let late = msger.request_with_envelope::<_, i32>(Envelope::new().with_ttl(Duration::from_secs(60)), 4).unwrap();
+    assert_eq!(late.wait().unwrap(), 4);
+
+    assert_eq!(*received.lock().unwrap(), vec![2]);
+    assert_eq!(msger.stats().unwrap().expired, 2);
+}
+
+#[test]
+fn ask() {
+    struct Get;
+    struct Add(i32);
+
+    impl Request for Get {
+        type Response = i32;
+    }
+
+    impl Request for Add {
+        type Response = Result<i32, String>;
+    }
+
+    struct Counter(i32);
+
+    impl Counter {
+        fn add(&mut self, Add(val): Add) -> Result<i32, String> {
+            self.0 = self.0.checked_add(val).ok_or_else(|| String::from("overflow"))?;
+            Ok(self.0)
+        }
+    }
+
+    impl Unit for Counter {
+        fn on_message(&mut self, _: Content) {}
+        fn on_message_with_reply(&mut self, data: Content) -> Content {
+            data
+        }
+    }
+
+    let msger = Counter(0).build_unit()
+        .on_request(|obj: &mut Counter, _: Get| obj.0)
+        .on_request(Counter::add)
+        .spawn_pipe();
+
+    msger.send(Add(2)).unwrap();
+    assert_eq!(msger.ask(Get).unwrap(), 2);
+    assert_eq!(msger.ask(Add(3)).unwrap(), Ok(5));
+    assert_eq!(msger.ask(Add(i32::MAX)).unwrap(), Err(String::from("overflow")));
+
+    let echo: &str = msger.send_with_reply("not a request").unwrap();
+    assert_eq!(echo, "not a request");
+}
+
+#[test]
+fn ask_shared() {
+    struct Get;
+
+    impl Request for Get {
+        type Response = i32;
+    }
+
+    struct Fallback;
+
+    impl Unit for Fallback {
+        fn on_message(&mut self, _: Content) {}
+        fn on_message_with_reply(&mut self, data: Content) -> Content {
+            data.is::<Get>().then_some("unit").into_content()
+        }
+    }
+
+    let msger = Fallback.build_unit().on_request(|_: &mut Fallback, _: Get| 1).spawn_pipe();
+
+    let held = Content::from_arc(Arc::new(Get));
+    let rep: Option<&str> = msger.send_with_reply(held.share().unwrap()).unwrap();
+    assert_eq!(rep, Some("unit"));
+
+    assert_eq!(msger.ask(Get).unwrap(), 1);
+}
+
+#[test]
+fn lifecycle() {
+    struct Lifecycle(Arc<Mutex<Vec<String>>>);
+
+    impl Unit for Lifecycle {
+        fn on_message(&mut self, data: Content) {
+            self.0.lock().unwrap().push(format!("msg {}", data.into::<i32>().unwrap()));
+        }
+
+        fn on_message_with_reply(&mut self, data: Content) -> Content {
+            data
+        }
+
+        fn on_start(&mut self) {
+            self.0.lock().unwrap().push(String::from("start"));
+        }
+
+        fn on_stop(&mut self, reason: StopReason) {
+            self.0.lock().unwrap().push(format!("stop {:?}", reason));
+        }
+
+        fn on_idle(&mut self) {
+            self.0.lock().unwrap().push(String::from("idle"));
+        }
+    }
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let msger = Lifecycle(Arc::clone(&events)).build_unit().spawn_pipe();
+
+    msger.send(1).unwrap();
+    let _: () = msger.send_with_reply(()).unwrap();
+    drop(msger);
+
+    let events = events.lock().unwrap();
+    assert_eq!(events.first().unwrap(), "start");
+    assert_eq!(events[1], "msg 1");
+    assert!(events.contains(&String::from("idle")));
+    assert_eq!(events.last().unwrap(), "stop Disconnected");
+}
+
+#[test]
+fn panic_isolation() {
+    struct Fragile(Arc<Barrier>, crossbeam_channel::Sender<StopReason>);
+
+    impl Unit for Fragile {
+        fn on_message(&mut self, _: Content) {
+            self.0.wait();
+            panic!("boom");
+        }
+
+        fn on_message_with_reply(&mut self, data: Content) -> Content {
+            match data.into::<&str>() {
+                Some("boom") => panic!("boom {}", 1),
+                _ => ().into_content()
+            }
+        }
+
+        fn on_stop(&mut self, reason: StopReason) {
+            self.1.send(reason).unwrap();
+        }
+    }
+
+    let barrier = Arc::new(Barrier::new(2));
+    let (tx, rx) = crossbeam_channel::unbounded();
+
+    let msger = Fragile(Arc::clone(&barrier), tx.clone()).build_unit().spawn_pipe();
+    assert_eq!(msger.send_with_reply::<_, ()>("boom").err().unwrap().to_string(), "failed to recv reply: unit panicked: boom 1");
+    assert_eq!(rx.recv().unwrap(), StopReason::Panicked);
+
+    let msger = Fragile(Arc::clone(&barrier), tx).build_unit().spawn_pipe();
+    msger.send(()).unwrap();
+    let pending = msger.request::<_, ()>("queued").unwrap();
+    barrier.wait();
+
+    assert_eq!(pending.wait().err(), Some(ReplyError::UnitPanicked(String::from("boom"))));
+    assert_eq!(rx.recv().unwrap(), StopReason::Panicked);
+}
+
+#[test]
+fn handlers() {
+    struct Spawn(&'static str);
+    struct Query;
+
+    impl Request for Query {
+        type Response = Vec<&'static str>;
+    }
+
+    #[derive(Default)]
+    struct Scheduler(Vec<&'static str>);
+
+    impl Handler<Spawn> for Scheduler {
+        type Reply = usize;
+
+        fn handle(&mut self, Spawn(name): Spawn) -> usize {
+            self.0.push(name);
+            self.0.len()
+        }
+    }
+
+    impl Handler<Query> for Scheduler {
+        type Reply = Vec<&'static str>;
+
+        fn handle(&mut self, _: Query) -> Vec<&'static str> {
+            self.0.clone()
+        }
+    }
+
+    conversation::handlers!(Scheduler: Spawn; requests: Query; hooks {
+        fn on_start(&mut self) {
+            self.0.push("init");
+        }
+    });
+
+    let msger = Scheduler::default().build_unit().spawn_pipe();
+
+    msger.send(Spawn("a")).unwrap();
+    msger.send(42).unwrap();
+    assert_eq!(msger.send_with_reply::<_, usize>(Spawn("b")).unwrap(), 3);
+    assert_eq!(msger.ask(Query).unwrap(), vec!["init", "a", "b"]);
+    assert!(msger.send_with_reply::<_, ()>(42).is_ok());
+
+    #[derive(Default)]
+    struct Strict(Vec<&'static str>);
+
+    impl Strict {
+        fn unknown(&mut self, data: Content) -> Content {
+            self.0.push(data.type_name());
+            self.0.len().into_content()
+        }
+    }
+
+    impl Handler<Query> for Strict {
+        type Reply = Vec<&'static str>;
+
+        fn handle(&mut self, _: Query) -> Vec<&'static str> {
+            self.0.clone()
+        }
+    }
+
+    conversation::handlers!(Strict; requests: Query; fallback = Self::unknown);
+
+    let msger = Strict::default().build_unit().spawn_pipe();
+
+    msger.send(1u8).unwrap();
+    assert_eq!(msger.send_with_reply::<_, usize>("two").unwrap(), 2);
+    assert_eq!(msger.ask(Query).unwrap(), vec!["u8", "&str"]);
 }
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use conversation::{Unit, Builder, WithContext, MessageSender, Content, IntoContent};
+use conversation::{Unit, Builder, WithContext, MessageSender, Content, IntoContent, match_content};
 
 use crate::runner::Runner;
 use crate::commands::*;
@@ -50,19 +50,12 @@ impl<T: Send + 'static> Exec<T> {
 
 impl<T: Send + 'static> Unit for Exec<T> {
     fn on_message(&mut self, msg: Content) {
-        if msg.is::<Spawn<T>>() {
-            if let Some(Spawn::<T>(name, arg)) = msg.into() {
-                self.new_task(name, arg)
-            }
-        } else if msg.is::<Abort>() {
-            if let Some(Abort(name)) = msg.into() {
-                self.abort(name)
-            }
-        } else if msg.is::<Wait>() {
-            if let Some(Wait) = msg.into() {
-                self.wait()
-            }
-        }
+        match_content!(msg, {
+            Spawn<T> => |Spawn(name, arg)| self.new_task(name, arg),
+            Abort => |Abort(name)| self.abort(name),
+            Wait => |_| self.wait(),
+            _ => {}
+        })
     }
 
     fn on_message_with_reply(&mut self, _: Content) -> Content { ().into_content() }
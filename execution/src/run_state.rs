@@ -3,18 +3,14 @@ use std::sync::RwLock;
 
 /* ---------- */
 
+#[derive(Default)]
 enum RunStatePrivate {
+    #[default]
     Running,
     Aborted,
     RunnerDropped,
 }
 
-impl Default for RunStatePrivate {
-    fn default() -> Self {
-        Self::Running
-    }
-}
-
 /* ---------- */
 
 pub struct RunState(Arc<RwLock<RunStatePrivate>>);
@@ -2,7 +2,9 @@ use std::sync::Arc;
 
 use crate::run_state::RunState;
 
-pub struct Runtime<T: Send + 'static>(Arc<dyn Fn(&RunState, T) + Sync + Send + 'static>);
+type RuntimeFn<T> = dyn Fn(&RunState, T) + Sync + Send + 'static;
+
+pub struct Runtime<T: Send + 'static>(Arc<RuntimeFn<T>>);
 
 impl<T: Send + 'static> Runtime<T> {
     pub fn new<F>(func: F) -> Self